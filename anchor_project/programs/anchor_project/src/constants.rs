@@ -1,15 +1,27 @@
 // Config constants
+// Config PDA: [SEED_CONFIG, instance_id], every other PDA below is namespaced by the config key
 pub const SEED_CONFIG: &[u8] = b"config";
 
 // Pool-related constants
 pub const SEED_POOL: &[u8] = b"pool";
 pub const MAX_POOLS_PER_INIT: u8 = 10;
 
+// Weight model constants
+pub const MAX_WEIGHT_TIERS: usize = 8;
+
 // Epoch result constants
 pub const SEED_EPOCH_RESULT: &[u8] = b"epoch_result";
 
 // Position constants
 pub const SEED_POSITION: &[u8] = b"position";
+// Each batched position takes three accounts (position, mint, ATA), kept under the transaction size limit
+pub const MAX_POSITIONS_PER_MINT: u8 = 5;
+
+// Position NFT constants
+// Position mint PDA: [SEED_POSITION_MINT, position]
+pub const SEED_POSITION_MINT: &[u8] = b"position_mint";
+pub const POSITION_NFT_SYMBOL: &str = "POS";
+pub const POSITION_NFT_URI: &str = "";
 
 // User state constants
 pub const SEED_USER_STATE: &[u8] = b"user_state";
@@ -18,4 +30,18 @@ pub const SEED_USER_STATE: &[u8] = b"user_state";
 pub const SEED_COMMITMENT: &[u8] = b"commitment";
 
 // Treasury constants
-pub const SEED_TREASURY: &[u8] = b"treasury";
\ No newline at end of file
+pub const SEED_TREASURY: &[u8] = b"treasury";
+
+// Pause flags (Config.paused bitmask)
+pub const PAUSE_MINT: u8 = 1 << 0;
+pub const PAUSE_COMMIT: u8 = 1 << 1;
+pub const PAUSE_RESOLVE: u8 = 1 << 2;
+pub const PAUSE_CLAIM: u8 = 1 << 3;
+pub const PAUSE_ALL: u8 = PAUSE_MINT | PAUSE_COMMIT | PAUSE_RESOLVE | PAUSE_CLAIM;
+
+// Seconds to wait for the VRF callback before the resolver may retry or fall back
+pub const DEFAULT_VRF_TIMEOUT: i64 = 300;
+
+// Fee constants
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_FEE_BPS: u16 = 1_000;
\ No newline at end of file
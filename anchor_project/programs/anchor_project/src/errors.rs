@@ -58,4 +58,112 @@ pub enum Errors {
 
     #[msg("Epoch has not ended yet - resolution not allowed")]
     EpochNotEnded,
+
+    #[msg("Unauthorized: Only the admin can call this function")]
+    UnauthorizedAdmin,
+
+    #[msg("Invalid position price - must be greater than zero")]
+    InvalidPositionPrice,
+
+    #[msg("Invalid epoch duration - must be greater than zero")]
+    InvalidEpochDuration,
+
+    #[msg("Invalid weight rate - denominator must be greater than zero")]
+    InvalidWeightRate,
+
+    #[msg("No pending admin to accept or cancel")]
+    NoPendingAdmin,
+
+    #[msg("No pending resolver to accept or cancel")]
+    NoPendingResolver,
+
+    #[msg("Unauthorized: Only the pending admin can accept")]
+    UnauthorizedPendingAdmin,
+
+    #[msg("Unauthorized: Only the pending resolver can accept")]
+    UnauthorizedPendingResolver,
+
+    #[msg("Invalid pool account - not a pool PDA of this instance")]
+    InvalidPoolAccount,
+
+    #[msg("Minting positions is paused")]
+    MintPaused,
+
+    #[msg("Committing positions is paused")]
+    CommitPaused,
+
+    #[msg("Resolution is paused")]
+    ResolvePaused,
+
+    #[msg("Claiming rewards is paused")]
+    ClaimPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Unauthorized: Only the admin or guardian can call this function")]
+    UnauthorizedGuardian,
+
+    #[msg("Invalid fee - exceeds the maximum basis points")]
+    InvalidFee,
+
+    #[msg("Invalid fee recipient - must be a token account of the allowed mint")]
+    InvalidFeeRecipient,
+
+    #[msg("No accrued fees to withdraw")]
+    NoFeesToWithdraw,
+
+    #[msg("Epoch is still active - wait for it to end and be resolved")]
+    EpochStillActive,
+
+    #[msg("Epoch resolution is pending - wait for the randomness callback")]
+    EpochResolutionPending,
+
+    #[msg("Epoch is not resolved")]
+    EpochNotResolved,
+
+    #[msg("Epoch is not pending resolution")]
+    EpochNotPending,
+
+    #[msg("Epoch cannot be cancelled - it is already resolved or cancelled")]
+    EpochNotCancellable,
+
+    #[msg("Epoch is not cancelled")]
+    EpochNotCancelled,
+
+    #[msg("VRF timeout has not elapsed yet")]
+    VrfTimeoutNotReached,
+
+    #[msg("Invalid VRF timeout - must be greater than zero")]
+    InvalidVrfTimeout,
+
+    #[msg("Slot hashes sysvar is empty")]
+    SlotHashesUnavailable,
+
+    #[msg("Automatic epoch rollover is disabled - pools_per_epoch is zero")]
+    AutoRolloverDisabled,
+
+    #[msg("Invalid weight model parameters")]
+    InvalidWeightModel,
+
+    #[msg("Position count must be between 1 and MAX_POSITIONS_PER_MINT")]
+    InvalidPositionCount,
+
+    #[msg("Position account does not belong to the signer")]
+    InvalidPositionAccount,
+
+    #[msg("Commitment account does not match the signer, pool and epoch")]
+    InvalidCommitmentAccount,
+
+    #[msg("EpochResult account does not belong to this config")]
+    InvalidEpochResultAccount,
+
+    #[msg("Positions are not transferable on this instance")]
+    PositionsNotTransferable,
+
+    #[msg("Cannot transfer a position to its current owner")]
+    InvalidTransferRecipient,
+
+    #[msg("Rent payer does not match the one that opened the epoch")]
+    InvalidRentPayer,
 }
\ No newline at end of file
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use crate::state::{WeightModel, ResolutionType, ResolutionPath};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AuthorityRole {
+    Admin,
+    Resolver,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub config: Pubkey,
+    pub role: AuthorityRole,
+    pub current: Pubkey,
+    pub pending: Pubkey,
+}
+
+#[event]
+pub struct AuthorityProposalCancelled {
+    pub config: Pubkey,
+    pub role: AuthorityRole,
+    pub pending: Pubkey,
+}
+
+#[event]
+pub struct AuthorityChanged {
+    pub config: Pubkey,
+    pub role: AuthorityRole,
+    pub previous: Pubkey,
+    pub current: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub config: Pubkey,
+    pub paused: u8,
+    pub updated_by: Pubkey,
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ConfigChange {
+    ResolutionType { resolution_type: ResolutionType },
+    PositionPrice { position_price: u64 },
+    EpochDuration { epoch_duration: i64 },
+    WeightModel {
+        weight_model: WeightModel,
+        weight_rate_numerator: u64,
+        weight_rate_denominator: u64,
+    },
+    Fees { mint_fee_bps: u16, claim_fee_bps: u16 },
+    FeeRecipient { fee_recipient_ata: Pubkey },
+    Guardian { guardian: Option<Pubkey> },
+    VrfTimeout { vrf_timeout: i64 },
+    PoolsPerEpoch { pools_per_epoch: u8 },
+    KeeperTip { keeper_tip: u64 },
+    TransferablePositions { transferable_positions: bool },
+    Resolver { resolver: Pubkey },
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub config: Pubkey,
+    pub updated_by: Pubkey,
+    pub change: ConfigChange,
+    // Staged changes only take effect when the next epoch is opened
+    pub staged: bool,
+}
+
+#[event]
+pub struct PositionMinted {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub global_id: u64,
+    pub user_index: u64,
+    pub price: u64,
+    pub fee: u64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct PoolsInitialized {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub pool_count: u8,
+    pub position_price: u64,
+    pub end_at: i64,
+}
+
+#[event]
+pub struct Committed {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub pool_id: u8,
+    pub position_id: u64,
+    pub weight: u64,
+    pub pool_total_positions: u64,
+    pub pool_total_weight: u64,
+}
+
+#[event]
+pub struct EpochResolutionRequested {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub requested_by: Pubkey,
+}
+
+#[event]
+pub struct EpochResolved {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub winning_pool_id: u8,
+    pub winning_weight: u64,
+    pub total_position_amount: u64,
+    pub path: ResolutionPath,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub pool_id: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub config: Pubkey,
+    pub fee_recipient_ata: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EpochCancelled {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct Refunded {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub pool_id: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct KeeperTipPaid {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionTransferred {
+    pub config: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub global_id: u64,
+    pub from_index: u64,
+    pub to_index: u64,
+    pub created_at: i64,
+}
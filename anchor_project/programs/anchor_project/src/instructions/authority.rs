@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::Errors;
+use crate::events::{AuthorityRole, AuthorityProposed, AuthorityProposalCancelled, AuthorityChanged};
+use crate::constants::SEED_CONFIG;
+use crate::instructions::UpdateConfig;
+
+// @note Admin and resolver rotation is two-step: the admin proposes a key,
+// the proposed key must sign accept_*, so a typo can never brick the deployment.
+
+pub fn propose_admin(
+    ctx: Context<UpdateConfig>,
+    new_admin: Pubkey,
+) -> Result<()> {
+    ctx.accounts.config.pending_admin = Some(new_admin);
+    emit_cpi!(AuthorityProposed {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Admin,
+        current: ctx.accounts.config.admin,
+        pending: new_admin,
+    });
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let previous = ctx.accounts.config.admin;
+    ctx.accounts.config.admin = ctx.accounts.signer.key();
+    ctx.accounts.config.pending_admin = None;
+    emit_cpi!(AuthorityChanged {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Admin,
+        previous,
+        current: ctx.accounts.config.admin,
+    });
+    Ok(())
+}
+
+pub fn cancel_admin_proposal(ctx: Context<UpdateConfig>) -> Result<()> {
+    let pending = ctx.accounts.config.pending_admin
+        .take()
+        .ok_or(error!(Errors::NoPendingAdmin))?;
+    emit_cpi!(AuthorityProposalCancelled {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Admin,
+        pending,
+    });
+    Ok(())
+}
+
+pub fn propose_resolver(
+    ctx: Context<UpdateConfig>,
+    new_resolver: Pubkey,
+) -> Result<()> {
+    ctx.accounts.config.pending_resolver = Some(new_resolver);
+    emit_cpi!(AuthorityProposed {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Resolver,
+        current: ctx.accounts.config.resolver,
+        pending: new_resolver,
+    });
+    Ok(())
+}
+
+pub fn accept_resolver(ctx: Context<AcceptResolver>) -> Result<()> {
+    let previous = ctx.accounts.config.resolver;
+    ctx.accounts.config.resolver = ctx.accounts.signer.key();
+    ctx.accounts.config.pending_resolver = None;
+    // An update_resolver staged earlier would undo the handover when the next epoch opens
+    ctx.accounts.config.pending_update.resolver = None;
+    emit_cpi!(AuthorityChanged {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Resolver,
+        previous,
+        current: ctx.accounts.config.resolver,
+    });
+    Ok(())
+}
+
+pub fn cancel_resolver_proposal(ctx: Context<UpdateConfig>) -> Result<()> {
+    let pending = ctx.accounts.config.pending_resolver
+        .take()
+        .ok_or(error!(Errors::NoPendingResolver))?;
+    emit_cpi!(AuthorityProposalCancelled {
+        config: ctx.accounts.config.key(),
+        role: AuthorityRole::Resolver,
+        pending,
+    });
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.pending_admin.is_some() @ Errors::NoPendingAdmin,
+        constraint = config.pending_admin == Some(signer.key()) @ Errors::UnauthorizedPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptResolver<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.pending_resolver.is_some() @ Errors::NoPendingResolver,
+        constraint = config.pending_resolver == Some(signer.key()) @ Errors::UnauthorizedPendingResolver,
+    )]
+    pub config: Account<'info, Config>,
+}
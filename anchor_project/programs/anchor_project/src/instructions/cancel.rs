@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Commitment, EpochResult, EpochResultState};
+use crate::errors::Errors;
+use crate::events::{EpochCancelled, Refunded};
+use crate::instructions::resolve::load_epoch_pools;
+use crate::constants::{
+    SEED_CONFIG,
+    SEED_EPOCH_RESULT,
+    SEED_COMMITMENT,
+    SEED_TREASURY,
+    PAUSE_CLAIM,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint,
+        TokenAccount,
+        TransferChecked,
+        transfer_checked,
+        TokenInterface
+    }
+};
+
+// @note An Active epoch is cancelled in place: all of its pools (passed in remaining_accounts,
+// ordered by id) are closed and current_epoch moves on. A Pending epoch was already rolled over by
+// resolve, so only the carried over positions need to go back into config.
+pub fn cancel_epoch<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelEpoch<'info>>,
+    epoch: u64,
+) -> Result<()> {
+    match ctx.accounts.epoch_result.epoch_result_state {
+        EpochResultState::Active => {
+            require!(epoch == ctx.accounts.config.current_epoch, Errors::EpochMismatch);
+
+            load_epoch_pools(
+                &ctx.accounts.config.key(),
+                epoch,
+                ctx.accounts.epoch_result.pool_count,
+                ctx.remaining_accounts,
+            )?;
+            for account_info in ctx.remaining_accounts.iter() {
+                // Delete pool pda
+                let pool_lamport = account_info.lamports();
+                **account_info.try_borrow_mut_lamports()? = 0;
+                **ctx.accounts.rent_payer.try_borrow_mut_lamports()? += pool_lamport;
+            }
+
+            ctx.accounts.config.current_epoch = epoch
+                .checked_add(1)
+                .ok_or(error!(Errors::InvalidAdd))?;
+        },
+        EpochResultState::Pending => {
+            ctx.accounts.config.remaining_total_position = ctx.accounts.config.remaining_total_position
+                .checked_add(ctx.accounts.epoch_result.carried_over_positions)
+                .ok_or(error!(Errors::InvalidAdd))?;
+            ctx.accounts.config.remaining_total_value = ctx.accounts.config.remaining_total_value
+                .checked_add(ctx.accounts.epoch_result.carried_over_value)
+                .ok_or(error!(Errors::InvalidAdd))?;
+        },
+        _ => return Err(error!(Errors::EpochNotCancellable)),
+    }
+
+    ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Cancelled;
+
+    emit_cpi!(EpochCancelled {
+        config: ctx.accounts.config.key(),
+        epoch,
+        cancelled_by: ctx.accounts.signer.key(),
+    });
+
+    Ok(())
+}
+
+/// Return the price paid for the committed positions of a cancelled epoch and close the commitment
+/// @note Mint fees are not refunded. Refunds are halted together with claims by PAUSE_CLAIM.
+pub fn refund(ctx: Context<RefundContext>, pool_id: u8, epoch: u64) -> Result<()> {
+    let amount = ctx.accounts.commitment.position_value;
+
+    let config_key = ctx.accounts.config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_TREASURY,
+        config_key.as_ref(),
+        &[ctx.bumps.treasury_pda],
+    ]];
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.treasury_ata.to_account_info(),
+        to: ctx.accounts.user_ata.to_account_info(),
+        authority: ctx.accounts.treasury_pda.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked(
+        cpi_context,
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit_cpi!(Refunded {
+        config: config_key,
+        owner: ctx.accounts.signer.key(),
+        epoch,
+        pool_id,
+        amount,
+    });
+
+    msg!("Refunded {} tokens for epoch: {}, pool: {}", amount, epoch, pool_id);
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct CancelEpoch<'info> {
+    #[account(
+        mut,
+        constraint = config.admin == signer.key() @ Errors::UnauthorizedAdmin
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Paid for the pools when the epoch was opened, receives their rent back
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump,
+        has_one = rent_payer @ Errors::InvalidRentPayer
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u8, epoch: u64)]
+pub struct RefundContext<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_CLAIM) @ Errors::ClaimPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_result.epoch_result_state == EpochResultState::Cancelled @ Errors::EpochNotCancelled,
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            SEED_COMMITMENT,
+            config.key().as_ref(),
+            signer.key().as_ref(),
+            pool_id.to_le_bytes().as_ref(),
+            epoch.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    /// CHECK: Treasury PDA that owns the treasury ATA
+    #[account(
+        seeds = [SEED_TREASURY, config.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = config.allowed_mint @ Errors::InvalidAdd,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
 use crate::{errors::Errors};
+use crate::instructions::fees::compute_fee;
+use crate::events::RewardClaimed;
 use crate::state::{
-    Commitment, EpochResult, Config
+    Commitment, EpochResult, EpochResultState, Config
 };
 use crate::constants::{
     SEED_CONFIG,
     SEED_EPOCH_RESULT,
     SEED_COMMITMENT,
-    SEED_TREASURY
+    SEED_TREASURY,
+    PAUSE_CLAIM
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -20,24 +23,156 @@ use anchor_spl::{
     }
 };
 
-pub fn claim(ctx: Context<ClaimContext>, pool_id: u8, epoch: u64) -> Result<()> {
-    let token_amount = ctx.accounts.epoch_result.total_position_amount
-        .checked_mul(ctx.accounts.config.position_price)
-        .ok_or(error!(crate::errors::Errors::InvalidMul))?;
-
-    let numerator = (ctx.accounts.commitment.weight as u128)
-        .checked_mul(token_amount as u128)
+/// Reward of a winning commitment, split into (payout, claim fee)
+fn reward_split(commitment: &Commitment, epoch_result: &EpochResult) -> Result<(u64, u64)> {
+    let numerator = (commitment.weight as u128)
+        .checked_mul(epoch_result.total_value as u128)
         .ok_or(error!(crate::errors::Errors::InvalidMul))?;
 
-    let reward = numerator
-        .checked_div(ctx.accounts.epoch_result.weight as u128)
+    let reward: u64 = numerator
+        .checked_div(epoch_result.weight as u128)
         .ok_or(error!(crate::errors::Errors::InvalidDiv))?
         .try_into()
         .map_err(|_| error!(crate::errors::Errors::InvalidMul))?; // Convert back to u64
 
+    // Claim fee stays in the treasury as accrued fees
+    let fee = compute_fee(reward, epoch_result.claim_fee_bps)?;
+    let payout = reward
+        .checked_sub(fee)
+        .ok_or(error!(crate::errors::Errors::InvalidCalculation))?;
+    Ok((payout, fee))
+}
+
+pub fn claim(ctx: Context<ClaimContext>, pool_id: u8, epoch: u64) -> Result<()> {
+    let (payout, fee) = reward_split(&ctx.accounts.commitment, &ctx.accounts.epoch_result)?;
+    ctx.accounts.config.accrued_fees = ctx.accounts.config.accrued_fees
+        .checked_add(fee)
+        .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
+
     let treasury_seed = SEED_TREASURY;
+    let config_key = ctx.accounts.config.key();
     let signer_seeds: &[&[&[u8]]] = &[&[
         treasury_seed,
+        config_key.as_ref(),
+        &[ctx.bumps.treasury_pda],
+    ]];
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.treasury_ata.to_account_info(),
+        to: ctx.accounts.user_ata.to_account_info(),
+        authority: ctx.accounts.treasury_pda.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked(
+        cpi_context,
+        payout,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit_cpi!(RewardClaimed {
+        config: config_key,
+        owner: ctx.accounts.signer.key(),
+        epoch,
+        pool_id,
+        amount: payout,
+        fee,
+    });
+
+    msg!("Claimed reward: {} tokens (fee: {}) for epoch: {}, pool: {}",
+        payout, fee, epoch, pool_id);
+
+    Ok(())
+}
+
+/// Claim several winning commitments with a single transfer from the treasury
+/// remaining_accounts holds (Commitment, EpochResult) pairs, each checked like claim
+/// and each commitment is closed
+// @note Remaining accounts share the 'info lifetime so they can be loaded as Account<T>
+pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    require!(
+        pairs.len() > 0 && pairs.remainder().is_empty(),
+        Errors::NotEnoughAccounts
+    );
+    let pair_count = pairs.len();
+
+    let config_key = ctx.accounts.config.key();
+    let signer_key = ctx.accounts.signer.key();
+    let mut total_payout: u64 = 0;
+    let mut total_fee: u64 = 0;
+
+    for pair in pairs {
+        let commitment = Account::<Commitment>::try_from(&pair[0])?;
+        let epoch_result = Account::<EpochResult>::try_from(&pair[1])?;
+        let epoch = epoch_result.epoch;
+        let pool_id = commitment.pool_id;
+
+        let (expected_epoch_result, _) = Pubkey::find_program_address(
+            &[SEED_EPOCH_RESULT, config_key.as_ref(), epoch.to_le_bytes().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(pair[1].key(), expected_epoch_result, Errors::InvalidEpochResultAccount);
+
+        let (expected_commitment, _) = Pubkey::find_program_address(
+            &[
+                SEED_COMMITMENT,
+                config_key.as_ref(),
+                signer_key.as_ref(),
+                pool_id.to_le_bytes().as_ref(),
+                epoch.to_le_bytes().as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(pair[0].key(), expected_commitment, Errors::InvalidCommitmentAccount);
+
+        require!(
+            epoch_result.epoch_result_state != EpochResultState::Active,
+            Errors::EpochStillActive
+        );
+        require!(
+            epoch_result.epoch_result_state != EpochResultState::Pending,
+            Errors::EpochResolutionPending
+        );
+        require!(
+            epoch_result.epoch_result_state == EpochResultState::Resolved,
+            Errors::EpochNotResolved
+        );
+        require!(epoch_result.winning_pool_id == pool_id, Errors::LosingPool);
+
+        let (payout, fee) = reward_split(&commitment, &epoch_result)?;
+        total_payout = total_payout
+            .checked_add(payout)
+            .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
+        total_fee = total_fee
+            .checked_add(fee)
+            .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
+
+        emit_cpi!(RewardClaimed {
+            config: config_key,
+            owner: signer_key,
+            epoch,
+            pool_id,
+            amount: payout,
+            fee,
+        });
+
+        commitment.close(ctx.accounts.signer.to_account_info())?;
+    }
+
+    ctx.accounts.config.accrued_fees = ctx.accounts.config.accrued_fees
+        .checked_add(total_fee)
+        .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_TREASURY,
+        config_key.as_ref(),
         &[ctx.bumps.treasury_pda],
     ]];
 
@@ -56,17 +191,18 @@ pub fn claim(ctx: Context<ClaimContext>, pool_id: u8, epoch: u64) -> Result<()>
 
     transfer_checked(
         cpi_context,
-        reward,
+        total_payout,
         ctx.accounts.token_mint.decimals,
     )?;
 
-    msg!("Claimed reward: {} tokens for epoch: {}, pool: {}",
-        reward, epoch, pool_id);
+    msg!("Claimed reward: {} tokens (fee: {}) for {} commitments",
+        total_payout, total_fee, pair_count);
 
     Ok(())
 }
 
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u8, epoch: u64)]
 pub struct ClaimContext<'info> {
@@ -74,14 +210,19 @@ pub struct ClaimContext<'info> {
     pub signer: Signer<'info>,
 
     #[account(
-        seeds = [SEED_CONFIG],
-        bump
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_CLAIM) @ Errors::ClaimPaused
     )]
     pub config: Account<'info, Config>,
 
     #[account(
-        seeds = [SEED_EPOCH_RESULT, epoch.to_le_bytes().as_ref()],
-        bump
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_result.epoch_result_state != EpochResultState::Active @ Errors::EpochStillActive,
+        constraint = epoch_result.epoch_result_state != EpochResultState::Pending @ Errors::EpochResolutionPending,
+        constraint = epoch_result.epoch_result_state == EpochResultState::Resolved @ Errors::EpochNotResolved,
     )]
     pub epoch_result: Account<'info, EpochResult>,
 
@@ -90,6 +231,7 @@ pub struct ClaimContext<'info> {
         close = signer,
         seeds = [
             SEED_COMMITMENT,
+            config.key().as_ref(),
             signer.key().as_ref(),
             pool_id.to_le_bytes().as_ref(),
             epoch.to_le_bytes().as_ref(),
@@ -101,7 +243,7 @@ pub struct ClaimContext<'info> {
 
     /// CHECK: Treasury PDA that owns the treasury ATA
     #[account(
-        seeds = [SEED_TREASURY],
+        seeds = [SEED_TREASURY, config.key().as_ref()],
         bump
     )]
     pub treasury_pda: AccountInfo<'info>,
@@ -129,4 +271,50 @@ pub struct ClaimContext<'info> {
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
-}
\ No newline at end of file
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_CLAIM) @ Errors::ClaimPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Treasury PDA that owns the treasury ATA
+    #[account(
+        seeds = [SEED_TREASURY, config.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = config.allowed_mint @ Errors::InvalidAdd,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -7,9 +7,23 @@ use crate::state::{
     EpochResult,
 };
 
-use crate::constants::{SEED_CONFIG, SEED_POSITION, SEED_POOL, SEED_COMMITMENT, SEED_EPOCH_RESULT};
+use crate::constants::{
+    SEED_CONFIG,
+    SEED_POSITION,
+    SEED_POSITION_MINT,
+    SEED_POOL,
+    SEED_COMMITMENT,
+    SEED_EPOCH_RESULT,
+    PAUSE_COMMIT
+};
 use crate::errors::Errors;
+use crate::events::Committed;
 use crate::instructions::weight::calculate_weight;
+use crate::instructions::position_nft::PositionNft;
+use anchor_spl::{
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
 
 pub fn commit(
     ctx: Context<Commit>,
@@ -29,6 +43,15 @@ pub fn commit(
         ctx.accounts.config.weight_rate_denominator,
     )?;
 
+    // The position token is burned along with the PositionAccount
+    PositionNft {
+        owner: &ctx.accounts.signer.to_account_info(),
+        config: &ctx.accounts.config,
+        position_mint: &ctx.accounts.position_mint.to_account_info(),
+        position_ata: &ctx.accounts.position_ata.to_account_info(),
+        token_program: &ctx.accounts.position_token_program.to_account_info(),
+    }.burn()?;
+
     ctx.accounts.pool.total_positions = ctx.accounts.pool.total_positions
         .checked_add(1)
         .ok_or(error!(Errors::InvalidAdd))?;
@@ -37,6 +60,10 @@ pub fn commit(
         .checked_add(weight)
         .ok_or(error!(Errors::InvalidAdd))?;
 
+    ctx.accounts.pool.total_value = ctx.accounts.pool.total_value
+        .checked_add(ctx.accounts.position.price)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
     // Initialize commitment on first use (init_if_needed creates zeroed account)
     if ctx.accounts.commitment.position_amount == 0 {
         ctx.accounts.commitment.user_pk = ctx.accounts.position.owner;
@@ -51,6 +78,20 @@ pub fn commit(
     ctx.accounts.commitment.position_amount = ctx.accounts.commitment.position_amount
         .checked_add(1)
         .ok_or(error!(Errors::InvalidAdd))?;
+    ctx.accounts.commitment.position_value = ctx.accounts.commitment.position_value
+        .checked_add(ctx.accounts.position.price)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
+    emit_cpi!(Committed {
+        config: ctx.accounts.config.key(),
+        owner: ctx.accounts.signer.key(),
+        epoch: ctx.accounts.pool.epoch,
+        pool_id: ctx.accounts.pool.id,
+        position_id: _position_id,
+        weight,
+        pool_total_positions: ctx.accounts.pool.total_positions,
+        pool_total_weight: ctx.accounts.pool.total_weight,
+    });
 
     msg!("Position committed - Weight: {}, Total positions: {}, Total weight: {}",
         weight, ctx.accounts.pool.total_positions, ctx.accounts.pool.total_weight);
@@ -58,7 +99,121 @@ pub fn commit(
     Ok(())
 }
 
+/// Commit several of the signer's positions to one pool in a single instruction
+/// remaining_accounts holds (position, position mint, position ATA) triples, weights
+/// are summed into the same Commitment, every position is closed and its token burned
+// @note Remaining accounts share the 'info lifetime so they can be loaded as Account<PositionAccount>
+pub fn commit_many<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CommitMany<'info>>,
+    _pool_id: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(ctx.accounts.epoch_result.end_at > current_time, Errors::EpochEnded);
+    let triples = ctx.remaining_accounts.chunks_exact(3);
+    require!(
+        triples.len() > 0 && triples.remainder().is_empty(),
+        Errors::NotEnoughAccounts
+    );
+    let position_count = triples.len() as u64;
+
+    let config_key = ctx.accounts.config.key();
+    let signer_key = ctx.accounts.signer.key();
+    let mut total_weight: u64 = 0;
+    let mut total_value: u64 = 0;
+
+    for accounts in triples {
+        let account_info = &accounts[0];
+        let position = Account::<PositionAccount>::try_from(account_info)?;
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[
+                SEED_POSITION,
+                config_key.as_ref(),
+                signer_key.as_ref(),
+                position.user_index.to_le_bytes().as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(account_info.key(), expected_pda, Errors::InvalidPositionAccount);
+        let (expected_mint, _) = Pubkey::find_program_address(
+            &[SEED_POSITION_MINT, expected_pda.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(accounts[1].key(), expected_mint, Errors::InvalidPositionAccount);
+
+        let weight = calculate_weight(
+            ctx.accounts.config.weight_model,
+            position.created_at,
+            current_time,
+            ctx.accounts.config.weight_rate_numerator,
+            ctx.accounts.config.weight_rate_denominator,
+        )?;
+        total_weight = total_weight
+            .checked_add(weight)
+            .ok_or(error!(Errors::InvalidAdd))?;
+        total_value = total_value
+            .checked_add(position.price)
+            .ok_or(error!(Errors::InvalidAdd))?;
+
+        // Pool totals move with each position so every event carries the running totals
+        let pool = &mut ctx.accounts.pool;
+        pool.total_positions = pool.total_positions
+            .checked_add(1)
+            .ok_or(error!(Errors::InvalidAdd))?;
+        pool.total_weight = pool.total_weight
+            .checked_add(weight)
+            .ok_or(error!(Errors::InvalidAdd))?;
+        pool.total_value = pool.total_value
+            .checked_add(position.price)
+            .ok_or(error!(Errors::InvalidAdd))?;
+
+        emit_cpi!(Committed {
+            config: config_key,
+            owner: signer_key,
+            epoch: ctx.accounts.pool.epoch,
+            pool_id: ctx.accounts.pool.id,
+            position_id: position.user_index,
+            weight,
+            pool_total_positions: ctx.accounts.pool.total_positions,
+            pool_total_weight: ctx.accounts.pool.total_weight,
+        });
+
+        PositionNft {
+            owner: &ctx.accounts.signer.to_account_info(),
+            config: &ctx.accounts.config,
+            position_mint: &accounts[1],
+            position_ata: &accounts[2],
+            token_program: &ctx.accounts.position_token_program.to_account_info(),
+        }.burn()?;
+        position.close(ctx.accounts.signer.to_account_info())?;
+    }
+
+    // Initialize commitment on first use (init_if_needed creates zeroed account)
+    if ctx.accounts.commitment.position_amount == 0 {
+        ctx.accounts.commitment.user_pk = signer_key;
+        ctx.accounts.commitment.pool_id = ctx.accounts.pool.id;
+        ctx.accounts.commitment.epoch = ctx.accounts.pool.epoch;
+    }
+
+    ctx.accounts.commitment.weight = ctx.accounts.commitment.weight
+        .checked_add(total_weight)
+        .ok_or(error!(Errors::InvalidAdd))?;
+    ctx.accounts.commitment.position_amount = ctx.accounts.commitment.position_amount
+        .checked_add(position_count)
+        .ok_or(error!(Errors::InvalidAdd))?;
+    ctx.accounts.commitment.position_value = ctx.accounts.commitment.position_value
+        .checked_add(total_value)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
+    msg!("{} positions committed - Weight: {}, Total positions: {}, Total weight: {}",
+        position_count, total_weight, ctx.accounts.pool.total_positions, ctx.accounts.pool.total_weight);
+
+    Ok(())
+}
+
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
     _position_id: u64,
@@ -70,14 +225,16 @@ pub struct Commit<'info> {
     pub signer: Signer<'info>,
 
     #[account(
-        seeds = [SEED_CONFIG],
-        bump
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_COMMIT) @ Errors::CommitPaused
     )]
     pub config: Account<'info, Config>,
 
     #[account(
         seeds = [
             SEED_EPOCH_RESULT,
+            config.key().as_ref(),
             config.current_epoch.to_le_bytes().as_ref()
         ],
         bump
@@ -90,6 +247,7 @@ pub struct Commit<'info> {
         close = signer,
         seeds = [
             SEED_POSITION,
+            config.key().as_ref(),
             signer.key().as_ref(),
             _position_id.to_le_bytes().as_ref()
         ],
@@ -97,10 +255,27 @@ pub struct Commit<'info> {
     )]
     pub position: Account<'info, PositionAccount>,
 
+    /// CHECK: Position's Token-2022 mint, closed after the burn
+    #[account(
+        mut,
+        seeds = [SEED_POSITION_MINT, position.key().as_ref()],
+        bump
+    )]
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = position_token_program,
+    )]
+    pub position_ata: InterfaceAccount<'info, TokenAccount>,
+
     #[account(
         mut,
         seeds = [
             SEED_POOL,
+            config.key().as_ref(),
             _pool_id.to_le_bytes().as_ref(),
             config.current_epoch.to_le_bytes().as_ref()
         ],
@@ -115,6 +290,7 @@ pub struct Commit<'info> {
         space = 8 + Commitment::INIT_SPACE,
         seeds = [
             SEED_COMMITMENT,
+            config.key().as_ref(),
             signer.key().as_ref(),
             pool.id.to_le_bytes().as_ref(),
             pool.epoch.to_le_bytes().as_ref(),
@@ -123,5 +299,63 @@ pub struct Commit<'info> {
     )]
     pub commitment: Account<'info, Commitment>,
 
+    pub position_token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(_pool_id: u8)]
+pub struct CommitMany<'info> {
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_COMMIT) @ Errors::CommitPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SEED_EPOCH_RESULT,
+            config.key().as_ref(),
+            config.current_epoch.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_POOL,
+            config.key().as_ref(),
+            _pool_id.to_le_bytes().as_ref(),
+            config.current_epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = pool.epoch == config.current_epoch @ Errors::EpochMismatch
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Commitment::INIT_SPACE,
+        seeds = [
+            SEED_COMMITMENT,
+            config.key().as_ref(),
+            signer.key().as_ref(),
+            pool.id.to_le_bytes().as_ref(),
+            pool.epoch.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    pub position_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::Errors;
+use crate::events::{ConfigChange, ConfigUpdated, FeesWithdrawn};
+use crate::constants::{SEED_CONFIG, SEED_TREASURY, BPS_DENOMINATOR, MAX_FEE_BPS};
+use crate::instructions::UpdateConfig;
+use anchor_spl::token_interface::{
+    Mint,
+    TokenAccount,
+    TransferChecked,
+    transfer_checked,
+    TokenInterface
+};
+
+/// Fee owed on `amount` at `fee_bps` basis points, rounded down
+pub fn compute_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(error!(Errors::InvalidMul))?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(error!(Errors::InvalidDiv))?;
+    u64::try_from(fee).map_err(|_| error!(Errors::InvalidMul))
+}
+
+/// Stage new fee rates, applied with the rest of pending_update on the next epoch
+pub fn update_fees(
+    ctx: Context<UpdateConfig>,
+    mint_fee_bps: u16,
+    claim_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        mint_fee_bps <= MAX_FEE_BPS && claim_fee_bps <= MAX_FEE_BPS,
+        Errors::InvalidFee
+    );
+    let pending = &mut ctx.accounts.config.pending_update;
+    pending.mint_fee_bps = Some(mint_fee_bps);
+    pending.claim_fee_bps = Some(claim_fee_bps);
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::Fees { mint_fee_bps, claim_fee_bps },
+        staged: true,
+    });
+    Ok(())
+}
+
+pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>) -> Result<()> {
+    ctx.accounts.config.fee_recipient_ata = ctx.accounts.fee_recipient_ata.key();
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::FeeRecipient {
+            fee_recipient_ata: ctx.accounts.config.fee_recipient_ata,
+        },
+        staged: false,
+    });
+    Ok(())
+}
+
+/// Move accrued fees out of the treasury
+/// @note Only config.accrued_fees is ever transferred, reward funds stay untouched
+pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+    let amount = ctx.accounts.config.accrued_fees;
+    require!(amount > 0, Errors::NoFeesToWithdraw);
+    ctx.accounts.config.accrued_fees = 0;
+
+    let config_key = ctx.accounts.config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_TREASURY,
+        config_key.as_ref(),
+        &[ctx.bumps.treasury_pda],
+    ]];
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.treasury_ata.to_account_info(),
+        to: ctx.accounts.fee_recipient_ata.to_account_info(),
+        authority: ctx.accounts.treasury_pda.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked(
+        cpi_context,
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit_cpi!(FeesWithdrawn {
+        config: config_key,
+        fee_recipient_ata: ctx.accounts.fee_recipient_ata.key(),
+        amount,
+    });
+
+    msg!("Withdrew {} tokens of protocol fees", amount);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeRecipient<'info> {
+    #[account(
+        constraint = config.admin == signer.key() @ Errors::UnauthorizedAdmin
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = fee_recipient_ata.mint == config.allowed_mint @ Errors::InvalidFeeRecipient,
+    )]
+    pub fee_recipient_ata: InterfaceAccount<'info, TokenAccount>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        constraint = config.admin == signer.key() @ Errors::UnauthorizedAdmin
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Treasury PDA that owns the treasury ATA
+    #[account(
+        seeds = [SEED_TREASURY, config.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = config.treasury_ata,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = config.fee_recipient_ata @ Errors::InvalidFeeRecipient,
+    )]
+    pub fee_recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = config.allowed_mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,41 +1,62 @@
-use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{TokenAccount, TokenInterface},
 };
 use crate::{errors::Errors};
 use crate::state::{Config, WeightModel, ResolutionType};
-use crate::constants::{SEED_CONFIG, SEED_TREASURY};
+use crate::constants::{SEED_CONFIG, SEED_TREASURY, DEFAULT_VRF_TIMEOUT};
+use crate::instructions::weight::validate_weight_model;
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize(
     ctx: Context<Initialize>,
     weight_model: WeightModel,
     resolution_type: ResolutionType,
     resolver: Pubkey,
     epoch_duration: i64,
+    position_price: u64,
     weight_rate_numerator: u64,
     weight_rate_denominator: u64,
+    instance_id: u64,
 ) -> Result<()> {
+    require!(position_price > 0, Errors::InvalidPositionPrice);
+    require!(epoch_duration > 0, Errors::InvalidEpochDuration);
+    require!(weight_rate_denominator > 0, Errors::InvalidWeightRate);
+    validate_weight_model(&weight_model)?;
+
     let config = &mut ctx.accounts.config;
+    config.instance_id = instance_id;
+    config.bump = ctx.bumps.config;
     config.admin = ctx.accounts.signer.to_account_info().key();
     config.resolver = resolver;
     config.current_epoch = 1;
     config.epoch_duration = epoch_duration;
     config.total_positions_minted = 0;
     config.remaining_total_position = 0;
-    config.position_price = LAMPORTS_PER_SOL
-        .checked_mul(1000)
-        .ok_or(error!(Errors::InvalidMul))?;
+    config.remaining_total_value = 0;
+    config.position_price = position_price;
     config.allowed_mint = ctx.accounts.mint.key();
     config.treasury_ata = ctx.accounts.treasury_ata.key();
     config.weight_model = weight_model;
     config.resolution_type = resolution_type;
     config.weight_rate_numerator = weight_rate_numerator;
     config.weight_rate_denominator = weight_rate_denominator;
+    config.vrf_timeout = DEFAULT_VRF_TIMEOUT;
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(
+    weight_model: WeightModel,
+    resolution_type: ResolutionType,
+    resolver: Pubkey,
+    epoch_duration: i64,
+    position_price: u64,
+    weight_rate_numerator: u64,
+    weight_rate_denominator: u64,
+    instance_id: u64,
+)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -47,14 +68,14 @@ pub struct Initialize<'info> {
         init,
         payer = signer,
         space = 8 + Config::INIT_SPACE,
-        seeds = [SEED_CONFIG],
+        seeds = [SEED_CONFIG, instance_id.to_le_bytes().as_ref()],
         bump
     )]
     pub config: Account<'info, Config>,
 
     /// CHECK: Tresury pda to sign transfer
     #[account(
-        seeds = [SEED_TREASURY],
+        seeds = [SEED_TREASURY, config.key().as_ref()],
         bump
     )]
     pub treasury_pda: AccountInfo<'info>,
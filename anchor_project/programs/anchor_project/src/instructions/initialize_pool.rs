@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
 
-use anchor_lang::solana_program::system_instruction::create_account;
-use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::Discriminator;
 
+use crate::instructions::pda::create_pda_account;
 use crate::state::{Config, Pool, EpochResult, EpochResultState};
 use crate::{errors::Errors};
+use crate::events::PoolsInitialized;
 use crate::constants::{
     SEED_CONFIG,
     SEED_EPOCH_RESULT,
     SEED_POOL,
+    PAUSE_RESOLVE,
     MAX_POOLS_PER_INIT
 };
 
@@ -19,60 +20,88 @@ use crate::constants::{
 pub fn initialize_pool<'info>(
     ctx: Context<'_, '_, '_, 'info, StartContext<'info>>,
     num_pools: u8) -> Result<()> {
+    open_epoch(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        ctx.remaining_accounts,
+        num_pools,
+        ctx.program_id,
+    )?;
+
+    emit_cpi!(PoolsInitialized {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.epoch_result.epoch,
+        pool_count: num_pools,
+        position_price: ctx.accounts.epoch_result.position_price,
+        end_at: ctx.accounts.epoch_result.end_at,
+    });
+
+    Ok(())
+}
+
+/// Create the pool PDAs of the current epoch and start its EpochResult
+/// Shared by initialize_pool and the permissionless crank
+pub(crate) fn open_epoch<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    config: &mut Account<'info, Config>,
+    epoch_result: &mut Account<'info, EpochResult>,
+    pool_accounts: &[AccountInfo<'info>],
+    num_pools: u8,
+    program_id: &Pubkey,
+) -> Result<()> {
     require!(num_pools <= MAX_POOLS_PER_INIT, Errors::TooManyPools);
     require!(
-        ctx.remaining_accounts.len() >= num_pools as usize,
+        pool_accounts.len() >= num_pools as usize,
         Errors::NotEnoughAccounts
     );
-    let current_epoch = ctx.accounts.config.current_epoch;
+    let current_epoch = config.current_epoch;
+    let config_key = config.key();
     let space = 8 + Pool::INIT_SPACE;
     let rent = Rent::get()?;
     let lamports = rent.minimum_balance(space);
 
-    for (i, remaining_account) in ctx.remaining_accounts
+    for (i, remaining_account) in pool_accounts
         .iter().take(num_pools as usize).enumerate() {
         let index = i as u8;
         let seeds = &[
             SEED_POOL,
+            config_key.as_ref(),
             &index.to_be_bytes(),
             &current_epoch.to_le_bytes(),
         ];
         let (expected_pda, bump) = Pubkey::find_program_address(
             seeds,
-            ctx.program_id
+            program_id
         );
         require_keys_eq!(remaining_account.key(), expected_pda);
 
         let seeds_with_bump = &[
             SEED_POOL,
+            config_key.as_ref(),
             &index.to_be_bytes(),
             &current_epoch.to_le_bytes(),
             &bump.to_le_bytes(),
         ];
 
-        let ix = create_account(
-            ctx.accounts.signer.key,
-            &expected_pda,
+        create_pda_account(
+            payer,
+            remaining_account,
+            system_program,
             lamports,
-            space as u64,
-            ctx.program_id,
-        );
-
-        invoke_signed(
-            &ix,
-            &[
-                ctx.accounts.signer.to_account_info(),
-                remaining_account.clone(),
-                ctx.accounts.system_program.to_account_info(),
-                ],
-            &[seeds_with_bump],
+            space,
+            program_id,
+            seeds_with_bump,
         )?;
 
         let mut data = remaining_account.try_borrow_mut_data()?;
         // Write the discriminator (8 bytes)
         data[0..8].copy_from_slice(&<Pool as Discriminator>::DISCRIMINATOR);
         // Now write the Pool struct fields in order
-        // Based on Pool struct: { total_positions: u64, total_weight: u64, id: u8, epoch: u64 }
+        // Based on Pool struct: { total_positions: u64, total_weight: u64, id: u8, epoch: u64, total_value: u64 }
+        // total_value is left zeroed by the allocation
         let mut offset = 8;
         // total_positions: u64 (8 bytes)
         data[offset..offset + 8].copy_from_slice(&0u64.to_le_bytes());
@@ -87,16 +116,23 @@ pub fn initialize_pool<'info>(
         data[offset..offset + 8].copy_from_slice(&current_epoch.to_le_bytes());
     }
 
+    // Staged admin updates take effect from this epoch onwards
+    config.apply_pending_update();
+
     let clock = Clock::get()?;
     let timestamp = clock.unix_timestamp;
-    ctx.accounts.epoch_result.epoch = current_epoch;
-    ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Active;
-    ctx.accounts.epoch_result.pool_count = num_pools;
-    ctx.accounts.epoch_result.end_at = timestamp + ctx.accounts.config.epoch_duration;
+    epoch_result.epoch = current_epoch;
+    epoch_result.rent_payer = payer.key();
+    epoch_result.epoch_result_state = EpochResultState::Active;
+    epoch_result.pool_count = num_pools;
+    epoch_result.position_price = config.position_price;
+    epoch_result.claim_fee_bps = config.claim_fee_bps;
+    epoch_result.end_at = timestamp + config.epoch_duration;
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(id: u8)]
 pub struct StartContext<'info>{
@@ -109,8 +145,9 @@ pub struct StartContext<'info>{
 
     #[account(
         mut,
-        seeds = [SEED_CONFIG],
-        bump
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
     )]
     pub config: Account<'info, Config>,
 
@@ -120,6 +157,7 @@ pub struct StartContext<'info>{
         space = 8 + EpochResult::INIT_SPACE,
         seeds = [
             SEED_EPOCH_RESULT,
+            config.key().as_ref(),
             config.current_epoch.to_le_bytes().as_ref()
         ],
         bump
@@ -1,9 +1,22 @@
 use anchor_lang::prelude::*;
 use crate::state::{Config, UserState, PositionAccount};
 use crate::errors::Errors;
-use crate::constants::{SEED_CONFIG, SEED_TREASURY, SEED_POSITION, SEED_USER_STATE};
+use crate::events::PositionMinted;
+use crate::instructions::fees::compute_fee;
+use crate::instructions::position_nft::PositionNft;
+use crate::instructions::pda::create_pda_account;
+use crate::constants::{
+    SEED_CONFIG,
+    SEED_TREASURY,
+    SEED_POSITION,
+    SEED_USER_STATE,
+    SEED_POSITION_MINT,
+    PAUSE_MINT,
+    MAX_POSITIONS_PER_MINT
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
+    token_2022::Token2022,
     token_interface::{
         Mint,
         TokenAccount,
@@ -24,6 +37,7 @@ pub fn mint_position(ctx: Context<MintPosition>) -> Result<()> {
     ctx.accounts.position.user_index = user_position_count;
     ctx.accounts.position.global_id = global_id;
     ctx.accounts.position.created_at = timestamp;
+    ctx.accounts.position.price = ctx.accounts.config.position_price;
 
     ctx.accounts.user_state.position_count = ctx.accounts.user_state.position_count
         .checked_add(1)
@@ -33,6 +47,17 @@ pub fn mint_position(ctx: Context<MintPosition>) -> Result<()> {
         .checked_add(1)
         .ok_or(error!(Errors::InvalidAdd))?;
 
+    // Mint fee is paid on top of the position price and tracked separately
+    // so it never becomes part of an epoch's reward pool
+    let position_price = ctx.accounts.config.position_price;
+    let fee = compute_fee(position_price, ctx.accounts.config.mint_fee_bps)?;
+    let amount = position_price
+        .checked_add(fee)
+        .ok_or(error!(Errors::InvalidAdd))?;
+    ctx.accounts.config.accrued_fees = ctx.accounts.config.accrued_fees
+        .checked_add(fee)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
     // Set transfer
 
     let transfer_accounts= TransferChecked{
@@ -49,14 +74,174 @@ pub fn mint_position(ctx: Context<MintPosition>) -> Result<()> {
 
     transfer_checked(
         cpi_context,
-        ctx.accounts.config.position_price,
+        amount,
         ctx.accounts.mint.decimals,
     )?;
 
+    PositionNft {
+        owner: &ctx.accounts.signer.to_account_info(),
+        config: &ctx.accounts.config,
+        position_mint: &ctx.accounts.position_mint.to_account_info(),
+        position_ata: &ctx.accounts.position_ata.to_account_info(),
+        token_program: &ctx.accounts.position_token_program.to_account_info(),
+    }.mint(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.position.key(),
+        &ctx.accounts.position,
+        &ctx.accounts.associated_token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    emit_cpi!(PositionMinted {
+        config: ctx.accounts.config.key(),
+        owner: ctx.accounts.signer.key(),
+        global_id,
+        user_index: user_position_count,
+        price: position_price,
+        fee,
+        created_at: timestamp,
+    });
+
     msg!("Created Position - Global ID: {}, User Index: {}", global_id, user_position_count);
     Ok(())
 }
 
+/// Mint `count` positions with a single transfer of count * (price + mint fee)
+/// remaining_accounts holds (position, position mint, position ATA) triples, in
+/// user_index order starting at user_state.position_count
+// @note Explicit 'info lifetime required because we iterate over ctx.remaining_accounts
+pub fn mint_positions<'info>(
+    ctx: Context<'_, '_, '_, 'info, MintPositions<'info>>,
+    count: u8,
+) -> Result<()> {
+    require!(
+        count > 0 && count <= MAX_POSITIONS_PER_MINT,
+        Errors::InvalidPositionCount
+    );
+    require!(
+        ctx.remaining_accounts.len() >= 3 * count as usize,
+        Errors::NotEnoughAccounts
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_key = ctx.accounts.config.key();
+    let signer_key = ctx.accounts.signer.key();
+    let space = 8 + PositionAccount::INIT_SPACE;
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let position_price = ctx.accounts.config.position_price;
+    let fee = compute_fee(position_price, ctx.accounts.config.mint_fee_bps)?;
+
+    for accounts in ctx.remaining_accounts.chunks_exact(3).take(count as usize) {
+        let position_account = &accounts[0];
+        let user_index = ctx.accounts.user_state.position_count;
+        let global_id = ctx.accounts.config.total_positions_minted;
+
+        let user_index_bytes = user_index.to_le_bytes();
+        let seeds = &[
+            SEED_POSITION,
+            config_key.as_ref(),
+            signer_key.as_ref(),
+            user_index_bytes.as_ref(),
+        ];
+        let (expected_pda, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
+        require_keys_eq!(position_account.key(), expected_pda);
+
+        create_pda_account(
+            &ctx.accounts.signer.to_account_info(),
+            position_account,
+            &ctx.accounts.system_program.to_account_info(),
+            lamports,
+            space,
+            ctx.program_id,
+            &[
+                SEED_POSITION,
+                config_key.as_ref(),
+                signer_key.as_ref(),
+                user_index_bytes.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        let position = PositionAccount {
+            owner: signer_key,
+            user_index,
+            global_id,
+            created_at: timestamp,
+            price: position_price,
+        };
+        position.try_serialize(&mut &mut position_account.try_borrow_mut_data()?[..])?;
+
+        PositionNft {
+            owner: &ctx.accounts.signer.to_account_info(),
+            config: &ctx.accounts.config,
+            position_mint: &accounts[1],
+            position_ata: &accounts[2],
+            token_program: &ctx.accounts.position_token_program.to_account_info(),
+        }.mint(
+            &ctx.accounts.signer.to_account_info(),
+            &expected_pda,
+            &position,
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+        )?;
+
+        ctx.accounts.user_state.position_count = user_index
+            .checked_add(1)
+            .ok_or(error!(Errors::InvalidAdd))?;
+        ctx.accounts.config.total_positions_minted = global_id
+            .checked_add(1)
+            .ok_or(error!(Errors::InvalidAdd))?;
+
+        emit_cpi!(PositionMinted {
+            config: config_key,
+            owner: signer_key,
+            global_id,
+            user_index,
+            price: position_price,
+            fee,
+            created_at: timestamp,
+        });
+    }
+
+    // One transfer for the whole batch, mint fees accrue as in mint_position
+    let total_fee = fee
+        .checked_mul(count as u64)
+        .ok_or(error!(Errors::InvalidMul))?;
+    let amount = position_price
+        .checked_mul(count as u64)
+        .ok_or(error!(Errors::InvalidMul))?
+        .checked_add(total_fee)
+        .ok_or(error!(Errors::InvalidAdd))?;
+    ctx.accounts.config.accrued_fees = ctx.accounts.config.accrued_fees
+        .checked_add(total_fee)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.user_ata.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.treasury_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts
+    );
+
+    transfer_checked(
+        cpi_context,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    msg!("Created {} positions - User position count: {}", count, ctx.accounts.user_state.position_count);
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MintPosition<'info> {
     #[account(mut)]
@@ -77,7 +262,7 @@ pub struct MintPosition<'info> {
 
     /// CHECK: Treasury PDA that owns the treasury ATA
     #[account(
-        seeds = [SEED_TREASURY],
+        seeds = [SEED_TREASURY, config.key().as_ref()],
         bump
     )]
     pub treasury_pda: AccountInfo<'info>,
@@ -94,7 +279,7 @@ pub struct MintPosition<'info> {
         init_if_needed,
         payer = signer,
         space = 8 + UserState::INIT_SPACE,
-        seeds = [SEED_USER_STATE, signer.key().as_ref()],
+        seeds = [SEED_USER_STATE, config.key().as_ref(), signer.key().as_ref()],
         bump
     )]
     pub user_state: Account<'info, UserState>,
@@ -106,6 +291,7 @@ pub struct MintPosition<'info> {
         space = 8 + PositionAccount::INIT_SPACE,
         seeds = [
             SEED_POSITION,
+            config.key().as_ref(),
             signer.key().as_ref(),
             user_state.position_count.to_le_bytes().as_ref()
         ],
@@ -113,14 +299,94 @@ pub struct MintPosition<'info> {
     )]
     pub position: Account<'info, PositionAccount>,
 
+    /// CHECK: NonTransferable Token-2022 mint mirroring the position, created by the handler
     #[account(
         mut,
-        seeds = [SEED_CONFIG],
+        seeds = [SEED_POSITION_MINT, position.key().as_ref()],
         bump
     )]
+    pub position_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Signer's ATA for position_mint, created by the handler
+    #[account(
+        mut,
+        seeds = [
+            signer.key().as_ref(),
+            position_token_program.key().as_ref(),
+            position_mint.key().as_ref()
+        ],
+        seeds::program = associated_token_program.key(),
+        bump
+    )]
+    pub position_ata: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_MINT) @ Errors::MintPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintPositions<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = config.allowed_mint @ Errors::InvalidAdd,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Treasury PDA that owns the treasury ATA
+    #[account(
+        seeds = [SEED_TREASURY, config.key().as_ref()],
+        bump
+    )]
+    pub treasury_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [SEED_USER_STATE, config.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_MINT) @ Errors::MintPaused
+    )]
     pub config: Account<'info, Config>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -5,6 +5,17 @@ pub mod initialize_pool;
 pub mod resolve;
 pub mod claim;
 pub mod udpate_resolution;
+pub mod update_config;
+pub mod authority;
+pub mod pause;
+pub mod fees;
+pub mod cancel;
+pub mod vrf_timeout;
+pub mod rollover;
+pub mod preview;
+pub mod transfer;
+pub mod position_nft;
+pub mod pda;
 pub mod weight;
 
 pub use initialize::*;
@@ -14,4 +25,13 @@ pub use initialize_pool::*;
 pub use resolve::*;
 pub use claim::*;
 pub use udpate_resolution::*;
+pub use update_config::*;
+pub use authority::*;
+pub use pause::*;
+pub use fees::*;
+pub use cancel::*;
+pub use vrf_timeout::*;
+pub use rollover::*;
+pub use preview::*;
+pub use transfer::*;
 pub use weight::*;
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::Errors;
+use crate::events::{PauseUpdated, ConfigChange, ConfigUpdated};
+use crate::constants::{SEED_CONFIG, PAUSE_ALL};
+use crate::instructions::UpdateConfig;
+
+/// Freeze the flows selected by `flags` (PAUSE_* bitmask)
+/// Callable by the admin or the guardian
+pub fn pause(ctx: Context<PauseContext>, flags: u8) -> Result<()> {
+    require!(flags != 0 && flags & !PAUSE_ALL == 0, Errors::InvalidPauseFlags);
+    ctx.accounts.config.paused |= flags;
+    emit_cpi!(PauseUpdated {
+        config: ctx.accounts.config.key(),
+        paused: ctx.accounts.config.paused,
+        updated_by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+/// Re-enable the flows selected by `flags`, admin only
+pub fn unpause(ctx: Context<UpdateConfig>, flags: u8) -> Result<()> {
+    require!(flags != 0 && flags & !PAUSE_ALL == 0, Errors::InvalidPauseFlags);
+    ctx.accounts.config.paused &= !flags;
+    emit_cpi!(PauseUpdated {
+        config: ctx.accounts.config.key(),
+        paused: ctx.accounts.config.paused,
+        updated_by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+pub fn set_guardian(ctx: Context<UpdateConfig>, guardian: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.guardian = guardian;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::Guardian { guardian },
+        staged: false,
+    });
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PauseContext<'info> {
+    #[account(
+        constraint = signer.key() == config.admin
+            || config.guardian == Some(signer.key()) @ Errors::UnauthorizedGuardian
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
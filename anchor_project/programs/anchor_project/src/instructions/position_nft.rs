@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{create, Create};
+use anchor_spl::token_interface::{
+    spl_token_2022::{
+        extension::ExtensionType,
+        instruction::AuthorityType,
+        state::Mint as MintState,
+    },
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+    burn, Burn,
+    close_account, CloseAccount,
+    initialize_mint2, InitializeMint2,
+    mint_to, MintTo,
+    set_authority, SetAuthority,
+    non_transferable_mint_initialize, NonTransferableMintInitialize,
+    metadata_pointer_initialize, MetadataPointerInitialize,
+    mint_close_authority_initialize, MintCloseAuthorityInitialize,
+    token_metadata_initialize, TokenMetadataInitialize,
+    token_metadata_update_field, TokenMetadataUpdateField,
+};
+use crate::state::{Config, PositionAccount};
+use crate::instructions::pda::create_pda_account;
+use crate::constants::{SEED_CONFIG, SEED_POSITION_MINT, POSITION_NFT_SYMBOL, POSITION_NFT_URI};
+
+/// Accounts needed to mint or burn the NFT mirroring a position
+/// `owner` holds the token, `config` is the mint, metadata update and close authority
+/// of every position mint
+pub(crate) struct PositionNft<'a, 'info> {
+    pub owner: &'a AccountInfo<'info>,
+    pub config: &'a Account<'info, Config>,
+    pub position_mint: &'a AccountInfo<'info>,
+    pub position_ata: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> PositionNft<'_, 'info> {
+    fn config_seeds(&self) -> [Vec<u8>; 3] {
+        [
+            SEED_CONFIG.to_vec(),
+            self.config.instance_id.to_le_bytes().to_vec(),
+            vec![self.config.bump],
+        ]
+    }
+
+    /// Create the position's NonTransferable Token-2022 mint with on-mint metadata
+    /// and mint its single token to the owner's ATA, then drop the mint authority
+    pub fn mint(
+        &self,
+        payer: &AccountInfo<'info>,
+        position_key: &Pubkey,
+        position: &PositionAccount,
+        associated_token_program: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        let config_key = self.config.key();
+        let mint_key = self.position_mint.key();
+        let (expected_mint, mint_bump) = Pubkey::find_program_address(
+            &[SEED_POSITION_MINT, position_key.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(mint_key, expected_mint);
+
+        let config_seeds = self.config_seeds();
+        let config_signer: &[&[&[u8]]] = &[&[&config_seeds[0], &config_seeds[1], &config_seeds[2]]];
+
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(config_key))?,
+            mint: mint_key,
+            name: format!("Position #{}", position.global_id),
+            symbol: POSITION_NFT_SYMBOL.to_string(),
+            uri: POSITION_NFT_URI.to_string(),
+            additional_metadata: vec![
+                ("global_id".to_string(), position.global_id.to_string()),
+                ("created_at".to_string(), position.created_at.to_string()),
+                ("weight_model".to_string(), self.config.weight_model.name().to_string()),
+            ],
+        };
+
+        // Fund the metadata up front, the token program reallocs the mint as it is written
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&[
+            ExtensionType::NonTransferable,
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+        ])?;
+        let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+
+        create_pda_account(
+            payer,
+            self.position_mint,
+            system_program,
+            lamports,
+            space,
+            self.token_program.key,
+            &[SEED_POSITION_MINT, position_key.as_ref(), &[mint_bump]],
+        )?;
+
+        // Extensions must be initialized before the mint itself
+        non_transferable_mint_initialize(CpiContext::new(
+            self.token_program.clone(),
+            NonTransferableMintInitialize {
+                token_program_id: self.token_program.clone(),
+                mint: self.position_mint.clone(),
+            },
+        ))?;
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.position_mint.clone(),
+                },
+            ),
+            Some(config_key),
+            Some(mint_key),
+        )?;
+        mint_close_authority_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                MintCloseAuthorityInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.position_mint.clone(),
+                },
+            ),
+            Some(&config_key),
+        )?;
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.clone(),
+                InitializeMint2 { mint: self.position_mint.clone() },
+            ),
+            0,
+            &config_key,
+            None,
+        )?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.clone(),
+                    metadata: self.position_mint.clone(),
+                    update_authority: self.config.to_account_info(),
+                    mint_authority: self.config.to_account_info(),
+                    mint: self.position_mint.clone(),
+                },
+                config_signer,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        for (key, value) in metadata.additional_metadata {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    TokenMetadataUpdateField {
+                        program_id: self.token_program.clone(),
+                        metadata: self.position_mint.clone(),
+                        update_authority: self.config.to_account_info(),
+                    },
+                    config_signer,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+
+        create(CpiContext::new(
+            associated_token_program.clone(),
+            Create {
+                payer: payer.clone(),
+                associated_token: self.position_ata.clone(),
+                authority: self.owner.clone(),
+                mint: self.position_mint.clone(),
+                system_program: system_program.clone(),
+                token_program: self.token_program.clone(),
+            },
+        ))?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                MintTo {
+                    mint: self.position_mint.clone(),
+                    to: self.position_ata.clone(),
+                    authority: self.config.to_account_info(),
+                },
+                config_signer,
+            ),
+            1,
+        )?;
+
+        // Supply is fixed at one
+        set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                SetAuthority {
+                    current_authority: self.config.to_account_info(),
+                    account_or_mint: self.position_mint.clone(),
+                },
+                config_signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )
+    }
+
+    /// Burn the owner's position token, then close its ATA and the mint, rent goes to the owner
+    pub fn burn(&self) -> Result<()> {
+        burn(
+            CpiContext::new(
+                self.token_program.clone(),
+                Burn {
+                    mint: self.position_mint.clone(),
+                    from: self.position_ata.clone(),
+                    authority: self.owner.clone(),
+                },
+            ),
+            1,
+        )?;
+
+        close_account(CpiContext::new(
+            self.token_program.clone(),
+            CloseAccount {
+                account: self.position_ata.clone(),
+                destination: self.owner.clone(),
+                authority: self.owner.clone(),
+            },
+        ))?;
+
+        let config_seeds = self.config_seeds();
+        close_account(CpiContext::new_with_signer(
+            self.token_program.clone(),
+            CloseAccount {
+                account: self.position_mint.clone(),
+                destination: self.owner.clone(),
+                authority: self.config.to_account_info(),
+            },
+            &[&[&config_seeds[0], &config_seeds[1], &config_seeds[2]]],
+        ))
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::{PositionAccount, Config, EpochResult};
+use crate::constants::{SEED_CONFIG, SEED_POSITION, SEED_EPOCH_RESULT, BPS_DENOMINATOR};
+use crate::errors::Errors;
+use crate::instructions::weight::calculate_weight;
+use crate::instructions::fees::compute_fee;
+use crate::instructions::resolve::load_epoch_pools;
+
+/// Outcome of committing a position to one pool of the current epoch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolPreview {
+    pub pool_id: u8,
+    /// Share of the pool weight after the commit, in basis points
+    pub share_bps: u64,
+    /// Reward after the claim fee if this pool wins and nothing else is committed
+    pub projected_reward: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WeightPreview {
+    pub weight: u64,
+    pub pools: Vec<PoolPreview>,
+}
+
+/// Weight the position would commit with right now, and what it would earn in each pool
+/// View only: clients simulate it and read the return data, nothing is written
+/// @note Every pool of the current epoch must be passed in remaining_accounts, ordered by id
+pub fn preview_weight<'info>(
+    ctx: Context<'_, '_, '_, 'info, PreviewWeight<'info>>,
+) -> Result<WeightPreview> {
+    let config = &ctx.accounts.config;
+    let epoch_result = &ctx.accounts.epoch_result;
+
+    let weight = calculate_weight(
+        config.weight_model,
+        ctx.accounts.position.created_at,
+        Clock::get()?.unix_timestamp,
+        config.weight_rate_numerator,
+        config.weight_rate_denominator,
+    )?;
+
+    let pools = load_epoch_pools(
+        &config.key(),
+        config.current_epoch,
+        epoch_result.pool_count,
+        ctx.remaining_accounts,
+    )?;
+
+    // Pot the winners split: carried over value, every committed position, plus this one
+    let pot = pools
+        .iter()
+        .try_fold(config.remaining_total_value, |total, pool| total.checked_add(pool.total_value))
+        .and_then(|total| total.checked_add(ctx.accounts.position.price))
+        .ok_or(error!(Errors::InvalidAdd))? as u128;
+
+    let pools = pools
+        .iter()
+        .map(|pool| {
+            let pool_weight = (pool.total_weight as u128)
+                .checked_add(weight as u128)
+                .ok_or(error!(Errors::InvalidAdd))?;
+            let share_bps = (weight as u128 * BPS_DENOMINATOR as u128 / pool_weight) as u64;
+            let reward: u64 = (weight as u128)
+                .checked_mul(pot)
+                .ok_or(error!(Errors::InvalidMul))?
+                .checked_div(pool_weight)
+                .ok_or(error!(Errors::InvalidDiv))?
+                .try_into()
+                .map_err(|_| error!(Errors::InvalidMul))?;
+            let fee = compute_fee(reward, epoch_result.claim_fee_bps)?;
+            Ok(PoolPreview {
+                pool_id: pool.id,
+                share_bps,
+                projected_reward: reward - fee,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(WeightPreview { weight, pools })
+}
+
+#[derive(Accounts)]
+pub struct PreviewWeight<'info> {
+    #[account(
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            SEED_EPOCH_RESULT,
+            config.key().as_ref(),
+            config.current_epoch.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    #[account(
+        seeds = [
+            SEED_POSITION,
+            config.key().as_ref(),
+            position.owner.as_ref(),
+            position.user_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub position: Account<'info, PositionAccount>,
+}
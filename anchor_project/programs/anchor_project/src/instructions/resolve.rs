@@ -1,77 +1,298 @@
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
-use crate::state::{Config, ResolutionType, Pool, EpochResult, EpochResultState};
+use crate::state::{Config, ResolutionType, ResolutionPath, Pool, EpochResult, EpochResultState};
 use crate::{errors::Errors};
-use crate::constants::{SEED_CONFIG, SEED_EPOCH_RESULT};
+use crate::events::{EpochResolutionRequested, EpochResolved, KeeperTipPaid};
+use crate::constants::{SEED_CONFIG, SEED_EPOCH_RESULT, SEED_POOL, PAUSE_RESOLVE};
 use ephemeral_vrf_sdk::anchor::vrf;
 use ephemeral_vrf_sdk::instructions::{create_request_randomness_ix, RequestRandomnessParams};
 use ephemeral_vrf_sdk::types::SerializableAccountMeta;
+use anchor_lang::solana_program::instruction::Instruction;
 
 pub fn callback_resolve(
     ctx: Context<CallbackResolve>,
     randomness: [u8; 32],
 ) -> Result<()> {
-    let pool_count = ctx.accounts.epoch_result.pool_count;
+    let max_pool_id = ctx.accounts.epoch_result.pool_count
+        .checked_sub(1)
+        .ok_or(error!(Errors::NoPoolsProvided))?;
     let rnd_u8 = ephemeral_vrf_sdk::rnd::random_u8_with_range(
         &randomness,
          0,
-         pool_count - 1
+         max_pool_id
     );
-    let winning_weight = ctx.accounts.epoch_result.pool_weights[rnd_u8 as usize];
+    let winning_weight = finalize_epoch(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        rnd_u8,
+        ResolutionPath::Vrf,
+    )?;
 
-    ctx.accounts.epoch_result.winning_pool_id = rnd_u8;
-    ctx.accounts.epoch_result.weight = winning_weight;
+    emit_cpi!(EpochResolved {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.epoch_result.epoch,
+        winning_pool_id: rnd_u8,
+        winning_weight,
+        total_position_amount: ctx.accounts.epoch_result.total_position_amount,
+        path: ResolutionPath::Vrf,
+    });
+
+    Ok(())
+}
+
+/// Store the winning pool and mark the epoch Resolved, returns the winning weight
+/// If nobody committed to the winner, the epoch's positions roll over to the next one
+/// @note Added, not assigned: a later epoch may already have been resolved while this
+/// one waited on its callback, and its own carry over must not be lost
+pub(crate) fn finalize_epoch(
+    config: &mut Config,
+    epoch_result: &mut EpochResult,
+    winning_pool_id: u8,
+    path: ResolutionPath,
+) -> Result<u64> {
+    let winning_weight = epoch_result.pool_weights[winning_pool_id as usize];
+
+    epoch_result.winning_pool_id = winning_pool_id;
+    epoch_result.weight = winning_weight;
 
     if winning_weight == 0 {
-        ctx.accounts.config.remaining_total_position = ctx.accounts.epoch_result.total_position_amount;
+        config.remaining_total_position = config.remaining_total_position
+            .checked_add(epoch_result.total_position_amount)
+            .ok_or(error!(Errors::InvalidAdd))?;
+        config.remaining_total_value = config.remaining_total_value
+            .checked_add(epoch_result.total_value)
+            .ok_or(error!(Errors::InvalidAdd))?;
     }
-    ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Resolved;
+    epoch_result.epoch_result_state = EpochResultState::Resolved;
+    epoch_result.resolution_path = path;
+    Ok(winning_weight)
+}
+
+/// Build the VRF request whose callback resolves `epoch_result`
+pub(crate) fn randomness_request_ix(
+    payer: Pubkey,
+    oracle_queue: Pubkey,
+    epoch_result: Pubkey,
+    config: Pubkey,
+    event_authority: Pubkey,
+    client_seed: u8,
+) -> Instruction {
+    // Callback accounts, in CallbackResolve order
+    let account_metas = vec![
+        SerializableAccountMeta {
+            pubkey: epoch_result,
+            is_signer: false,
+            is_writable: true,
+        },
+        SerializableAccountMeta {
+            pubkey: config,
+            is_signer: false,
+            is_writable: true,
+        },
+        // Needed by emit_cpi! in the callback
+        SerializableAccountMeta {
+            pubkey: event_authority,
+            is_signer: false,
+            is_writable: false,
+        },
+        SerializableAccountMeta {
+            pubkey: crate::ID,
+            is_signer: false,
+            is_writable: false,
+        },
+    ];
+
+    msg!("Requesting randomness...");
+    create_request_randomness_ix(RequestRandomnessParams {
+        payer,
+        oracle_queue,
+        callback_program_id: crate::ID,
+        callback_discriminator: crate::instruction::CallbackResolve::DISCRIMINATOR.to_vec(),
+        caller_seed: [client_seed; 32],
+        accounts_metas: Some(account_metas),
+        ..Default::default()
+    })
+}
 
+/// Deserialize a pool passed in remaining_accounts and make sure it is
+/// this instance's PDA, so pools of another instance can't be mixed in
+pub(crate) fn load_pool(
+    config_key: &Pubkey,
+    account_info: &AccountInfo,
+) -> Result<Pool> {
+    require_keys_eq!(*account_info.owner, crate::ID, Errors::InvalidPoolAccount);
+    let mut data: &[u8] = &account_info.try_borrow_data()?;
+    let pool = Pool::try_deserialize(&mut data)?;
+
+    let (expected_pda, _) = Pubkey::find_program_address(
+        &[
+            SEED_POOL,
+            config_key.as_ref(),
+            &pool.id.to_le_bytes(),
+            &pool.epoch.to_le_bytes(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(account_info.key(), expected_pda, Errors::InvalidPoolAccount);
+    Ok(pool)
+}
+
+/// Load all `pool_count` pools of `epoch` from remaining_accounts
+/// They must be passed ordered by id, so none can be left out or passed twice
+pub(crate) fn load_epoch_pools(
+    config_key: &Pubkey,
+    epoch: u64,
+    pool_count: u8,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<Pool>> {
+    require!(
+        remaining_accounts.len() == pool_count as usize,
+        Errors::InvalidPoolAccount
+    );
+    remaining_accounts
+        .iter()
+        .enumerate()
+        .map(|(index, account_info)| {
+            let pool = load_pool(config_key, account_info)?;
+            require!(pool.epoch == epoch, Errors::EpochMismatch);
+            require!(pool.id as usize == index, Errors::InvalidPoolAccount);
+            Ok(pool)
+        })
+        .collect()
+}
+
+/// Store the weight of each pool in epoch_result, done before the randomness
+/// request so the callback can read them
+pub(crate) fn store_pool_weights(
+    config_key: &Pubkey,
+    epoch_result: &mut EpochResult,
+    pool_accounts: &[AccountInfo],
+) -> Result<()> {
+    let pools = load_epoch_pools(
+        config_key,
+        epoch_result.epoch,
+        epoch_result.pool_count,
+        pool_accounts,
+    )?;
+    for pool in pools.iter() {
+        epoch_result.pool_weights[pool.id as usize] = pool.total_weight;
+    }
     Ok(())
 }
 
 /// Calculate totals, store pool weights, and delete pool PDAs
-fn weight_and_reward_split_with_delete<'info>(
-    accounts: &mut ResolveContext<'_>,
-    remaining_accounts: &[AccountInfo<'info>],
-) -> Result<()>{
-    let current_epoch = accounts.config.current_epoch;
+/// Returns the keeper tip paid to the signer out of the reclaimed pool rent
+fn weight_and_reward_split_with_delete(
+    config: &mut Account<'_, Config>,
+    epoch_result: &mut EpochResult,
+    signer: &AccountInfo,
+    rent_payer: &AccountInfo,
+    pool_accounts: &[AccountInfo],
+) -> Result<u64>{
+    let current_epoch = config.current_epoch;
+    let config_key = config.key();
 
     let mut total_position_amount: u64 = 0;
-    if accounts.config.remaining_total_position > 0 {
+    if config.remaining_total_position > 0 {
         total_position_amount = total_position_amount
-            .checked_add(accounts.config.remaining_total_position)
+            .checked_add(config.remaining_total_position)
             .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
-        accounts.config.remaining_total_position = 0;
+        epoch_result.carried_over_positions = config.remaining_total_position;
+        config.remaining_total_position = 0;
     }
+    let mut total_value = config.remaining_total_value;
+    epoch_result.carried_over_value = total_value;
+    config.remaining_total_value = 0;
 
-    let pool_count = remaining_accounts.len() as u8;
-    accounts.epoch_result.pool_count = pool_count;
+    let pools = load_epoch_pools(
+        &config_key,
+        current_epoch,
+        epoch_result.pool_count,
+        pool_accounts,
+    )?;
 
-    for account_info in remaining_accounts.iter() {
-        let mut data: &[u8] = &account_info.try_borrow_data()?;
-        let pool = Pool::try_deserialize(&mut data)?;
+    let mut reclaimed_rent: u64 = 0;
 
-        require!(pool.epoch == current_epoch, Errors::EpochMismatch);
+    for (pool, account_info) in pools.iter().zip(pool_accounts.iter()) {
         total_position_amount = total_position_amount
             .checked_add(pool.total_positions)
             .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
+        total_value = total_value
+            .checked_add(pool.total_value)
+            .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
 
-        accounts.epoch_result.pool_weights[pool.id as usize] = pool.total_weight;
+        epoch_result.pool_weights[pool.id as usize] = pool.total_weight;
 
         // Delete pool pda
         let pool_lamport = account_info.lamports();
         **account_info.try_borrow_mut_lamports()? = 0;
-        **accounts.signer.to_account_info().try_borrow_mut_lamports()? += pool_lamport;
+        reclaimed_rent = reclaimed_rent
+            .checked_add(pool_lamport)
+            .ok_or(error!(crate::errors::Errors::InvalidAdd))?;
     }
-    accounts.epoch_result.total_position_amount = total_position_amount;
-    accounts.epoch_result.epoch = current_epoch;
-    accounts.config.current_epoch = current_epoch
+
+    // Pool rent goes back to whoever opened the epoch, a keeper resolving in the
+    // resolver's place is tipped from it
+    let keeper_tip = if signer.key() == config.resolver {
+        0
+    } else {
+        config.keeper_tip.min(reclaimed_rent)
+    };
+    **signer.try_borrow_mut_lamports()? += keeper_tip;
+    **rent_payer.try_borrow_mut_lamports()? += reclaimed_rent - keeper_tip;
+
+    epoch_result.total_position_amount = total_position_amount;
+    epoch_result.total_value = total_value;
+    epoch_result.epoch = current_epoch;
+    config.current_epoch = current_epoch
         .checked_add(1)
         .ok_or(error!(Errors::InvalidAdd))?;
-    Ok(())
+    Ok(keeper_tip)
 }
 
+/// Outcome of settle_epoch, for the caller to emit the matching events
+pub(crate) enum Settlement {
+    Resolved { winning_pool_id: u8, winning_weight: u64 },
+    Requested { keeper_tip: u64 },
+}
+
+/// Close the pools of the current epoch and move on to the next one, shared by resolve and rollover
+/// Admin epochs are resolved to `client_seed` right away, Oracle epochs are left Pending:
+/// their randomness must already have been requested, the CPI has to come before any lamports move
+pub(crate) fn settle_epoch(
+    config: &mut Account<'_, Config>,
+    epoch_result: &mut EpochResult,
+    signer: &AccountInfo,
+    rent_payer: &AccountInfo,
+    pool_accounts: &[AccountInfo],
+    client_seed: u8,
+    current_time: i64,
+) -> Result<Settlement> {
+    epoch_result.epoch_result_state = EpochResultState::Pending;
+    let keeper_tip = weight_and_reward_split_with_delete(
+        config,
+        epoch_result,
+        signer,
+        rent_payer,
+        pool_accounts,
+    )?;
+
+    match config.resolution_type {
+        ResolutionType::Admin => {
+            // The admin picks the winner directly, it must be one of the epoch's pools
+            require!(client_seed < epoch_result.pool_count, Errors::InvalidPoolId);
+            // Read winning weight from stored array
+            let winning_weight = finalize_epoch(config, epoch_result, client_seed, ResolutionPath::Admin)?;
+            Ok(Settlement::Resolved { winning_pool_id: client_seed, winning_weight })
+        },
+        ResolutionType::Oracle => {
+            epoch_result.requested_at = current_time;
+            Ok(Settlement::Requested { keeper_tip })
+        },
+    }
+}
+
+/// Every pool of the epoch must be passed in remaining_accounts, ordered by id
 pub fn resolve(
     ctx: Context<ResolveContext>,
     client_seed: u8,
@@ -85,91 +306,103 @@ pub fn resolve(
         Errors::EpochNotEnded
     );
 
-    match ctx.accounts.config.resolution_type {
-        ResolutionType::Admin => {
-            ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Pending;
-            // Delete pools and store weights in epoch_result
-            weight_and_reward_split_with_delete(ctx.accounts, &ctx.remaining_accounts)?;
+    if ctx.accounts.config.resolution_type == ResolutionType::Oracle {
+        let ix = randomness_request_ix(
+            ctx.accounts.signer.key(),
+            ctx.accounts.oracle_queue.key(),
+            ctx.accounts.epoch_result.key(),
+            ctx.accounts.config.key(),
+            ctx.accounts.event_authority.key(),
+            client_seed,
+        );
+        // Store pool weights BEFORE CPI so callback can read them
+        store_pool_weights(
+            &ctx.accounts.config.key(),
+            &mut ctx.accounts.epoch_result,
+            ctx.remaining_accounts,
+        )?;
 
-            // Read winning weight from stored array
-            let weight = ctx.accounts.epoch_result.pool_weights[client_seed as usize];
-            if weight == 0 {
-                ctx.accounts.config.remaining_total_position = ctx.accounts.epoch_result.total_position_amount;
-            }
-            ctx.accounts.epoch_result.weight = weight;
-            ctx.accounts.epoch_result.winning_pool_id = client_seed;
-            ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Resolved;
+        // IMPORTANT: CPI must happen BEFORE lamport manipulation
+        // See: https://github.com/solana-labs/solana/issues/9711
+        ctx.accounts
+            .invoke_signed_vrf(&ctx.accounts.signer.to_account_info(), &ix)?;
+    }
+
+    // Delete pools and store weights in epoch_result
+    let settlement = settle_epoch(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.rent_payer.to_account_info(),
+        ctx.remaining_accounts,
+        client_seed,
+        current_time,
+    )?;
+
+    match settlement {
+        Settlement::Resolved { winning_pool_id, winning_weight } => {
+            emit_cpi!(EpochResolved {
+                config: ctx.accounts.config.key(),
+                epoch: ctx.accounts.epoch_result.epoch,
+                winning_pool_id,
+                winning_weight,
+                total_position_amount: ctx.accounts.epoch_result.total_position_amount,
+                path: ResolutionPath::Admin,
+            });
         },
-        ResolutionType::Oracle => {
-            // Callback accounts + signer (receives lamports from deleted pools)
-            let account_metas = vec![
-                SerializableAccountMeta {
-                    pubkey: ctx.accounts.epoch_result.key(),
-                    is_signer: false,
-                    is_writable: true,
-                },
-                SerializableAccountMeta {
-                    pubkey: ctx.accounts.config.key(),
-                    is_signer: false,
-                    is_writable: true,
-                },
-            ];
-
-            msg!("Requesting randomness...");
-            let ix = create_request_randomness_ix(RequestRandomnessParams {
-                payer: ctx.accounts.signer.key(),
-                oracle_queue: ctx.accounts.oracle_queue.key(),
-                callback_program_id: crate::ID,
-                callback_discriminator: crate::instruction::CallbackResolve::DISCRIMINATOR.to_vec(),
-                caller_seed: [client_seed; 32],
-                accounts_metas: Some(account_metas),
-                ..Default::default()
+        Settlement::Requested { keeper_tip } => {
+            emit_cpi!(EpochResolutionRequested {
+                config: ctx.accounts.config.key(),
+                epoch: ctx.accounts.epoch_result.epoch,
+                requested_by: ctx.accounts.signer.key(),
             });
-            // Store pool weights BEFORE CPI so callback can read them
-            ctx.accounts.epoch_result.pool_count = ctx.remaining_accounts.len() as u8;
-            for account_info in ctx.remaining_accounts.iter() {
-                let mut data: &[u8] = &account_info.try_borrow_data()?;
-                let pool = Pool::try_deserialize(&mut data)?;
-                ctx.accounts.epoch_result.pool_weights[pool.id as usize] = pool.total_weight;
-            }
 
-            // IMPORTANT: CPI must happen BEFORE lamport manipulation
-            // See: https://github.com/solana-labs/solana/issues/9711
-            ctx.accounts
-                .invoke_signed_vrf(&ctx.accounts.signer.to_account_info(), &ix)?;
-
-            // Now process totals and delete pools AFTER the CPI
-            weight_and_reward_split_with_delete(ctx.accounts, &ctx.remaining_accounts)?;
-            ctx.accounts.epoch_result.epoch_result_state = EpochResultState::Pending;
+            if keeper_tip > 0 {
+                emit_cpi!(KeeperTipPaid {
+                    config: ctx.accounts.config.key(),
+                    epoch: ctx.accounts.epoch_result.epoch,
+                    keeper: ctx.accounts.signer.key(),
+                    amount: keeper_tip,
+                });
+            }
         },
-        // _ => return Err(error![Errors::UnsupportedResolution]),
     }
     Ok(())
 }
 
 #[vrf]
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ResolveContext<'info> {
+    // Oracle epochs take their outcome from VRF, so any keeper may trigger them
     #[account(
         mut,
         constraint = signer.key() == config.resolver.key()
+            || config.resolution_type == ResolutionType::Oracle @ Errors::UnauthorizedResolver
     )]
     pub signer: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [SEED_CONFIG],
-        bump
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
     )]
     pub config: Account<'info, Config>,
 
+    /// CHECK: Paid for the pools when the epoch was opened, receives their rent back
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+
     #[account(
         mut,
         seeds = [
             SEED_EPOCH_RESULT,
+            config.key().as_ref(),
             config.current_epoch.to_le_bytes().as_ref()
         ],
-        bump
+        bump,
+        has_one = rent_payer @ Errors::InvalidRentPayer
     )]
     pub epoch_result: Account<'info, EpochResult>,
 
@@ -180,6 +413,7 @@ pub struct ResolveContext<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CallbackResolve<'info> {
     /// This check ensure that the vrf_program_identity (which is a PDA) is a signer
@@ -191,15 +425,16 @@ pub struct CallbackResolve<'info> {
     // because current_epoch was already incremented during resolve
     #[account(
         mut,
-        seeds = [SEED_EPOCH_RESULT, epoch_result.epoch.to_le_bytes().as_ref()],
-        bump
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch_result.epoch.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_result.epoch_result_state == EpochResultState::Pending @ Errors::EpochNotPending
     )]
     pub epoch_result: Account<'info, EpochResult>,
 
     #[account(
         mut,
-        seeds = [SEED_CONFIG],
-        bump
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
     )]
     pub config: Account<'info, Config>,
 }
\ No newline at end of file
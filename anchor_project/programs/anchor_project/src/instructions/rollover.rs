@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, EpochResult, ResolutionType, ResolutionPath};
+use crate::errors::Errors;
+use crate::events::{
+    ConfigChange,
+    ConfigUpdated,
+    PoolsInitialized,
+    EpochResolutionRequested,
+    EpochResolved,
+    KeeperTipPaid,
+};
+use crate::constants::{SEED_CONFIG, SEED_EPOCH_RESULT, PAUSE_RESOLVE, MAX_POOLS_PER_INIT};
+use crate::instructions::UpdateConfig;
+use crate::instructions::initialize_pool::open_epoch;
+use crate::instructions::resolve::{randomness_request_ix, settle_epoch, store_pool_weights, Settlement};
+use ephemeral_vrf_sdk::anchor::vrf;
+
+// @note With pools_per_epoch set, anyone can open the next epoch right after resolve.
+// rollover does both in one instruction so epochs roll over with no dead time.
+
+/// 0 disables the crank, epochs are then opened by the resolver with initialize_pool
+pub fn update_pools_per_epoch(ctx: Context<UpdateConfig>, pools_per_epoch: u8) -> Result<()> {
+    require!(pools_per_epoch <= MAX_POOLS_PER_INIT, Errors::TooManyPools);
+    ctx.accounts.config.pools_per_epoch = pools_per_epoch;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::PoolsPerEpoch { pools_per_epoch },
+        staged: false,
+    });
+    Ok(())
+}
+
+/// Tip in lamports for keepers resolving Oracle epochs, capped by the reclaimed pool rent
+pub fn update_keeper_tip(ctx: Context<UpdateConfig>, keeper_tip: u64) -> Result<()> {
+    ctx.accounts.config.keeper_tip = keeper_tip;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::KeeperTip { keeper_tip },
+        staged: false,
+    });
+    Ok(())
+}
+
+/// Permissionless: open the current epoch with config.pools_per_epoch pools
+/// Pool PDAs are passed in remaining_accounts, exactly like initialize_pool
+pub fn crank<'info>(ctx: Context<'_, '_, '_, 'info, Crank<'info>>) -> Result<()> {
+    let num_pools = ctx.accounts.config.pools_per_epoch;
+
+    open_epoch(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        ctx.remaining_accounts,
+        num_pools,
+        ctx.program_id,
+    )?;
+
+    emit_cpi!(PoolsInitialized {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.epoch_result.epoch,
+        pool_count: num_pools,
+        position_price: ctx.accounts.epoch_result.position_price,
+        end_at: ctx.accounts.epoch_result.end_at,
+    });
+
+    Ok(())
+}
+
+/// Resolve the current epoch and open the next one with config.pools_per_epoch pools
+/// remaining_accounts holds the pools of the epoch being resolved (as for resolve),
+/// followed by the pool PDAs of the next epoch (as for crank)
+/// The signer pays for the next epoch's pools and gets their rent back when it is resolved
+pub fn rollover<'info>(
+    ctx: Context<'_, '_, '_, 'info, Rollover<'info>>,
+    client_seed: u8,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time >= ctx.accounts.epoch_result.end_at,
+        Errors::EpochNotEnded
+    );
+
+    let pool_count = ctx.accounts.epoch_result.pool_count as usize;
+    require!(
+        ctx.remaining_accounts.len() >= pool_count,
+        Errors::NotEnoughAccounts
+    );
+    let (closing_pools, opening_pools) = ctx.remaining_accounts.split_at(pool_count);
+
+    if ctx.accounts.config.resolution_type == ResolutionType::Oracle {
+        let ix = randomness_request_ix(
+            ctx.accounts.signer.key(),
+            ctx.accounts.oracle_queue.key(),
+            ctx.accounts.epoch_result.key(),
+            ctx.accounts.config.key(),
+            ctx.accounts.event_authority.key(),
+            client_seed,
+        );
+        store_pool_weights(
+            &ctx.accounts.config.key(),
+            &mut ctx.accounts.epoch_result,
+            closing_pools,
+        )?;
+        // CPI before any lamports move, see resolve
+        ctx.accounts
+            .invoke_signed_vrf(&ctx.accounts.signer.to_account_info(), &ix)?;
+    }
+
+    let settlement = settle_epoch(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.rent_payer.to_account_info(),
+        closing_pools,
+        client_seed,
+        current_time,
+    )?;
+
+    match settlement {
+        Settlement::Resolved { winning_pool_id, winning_weight } => {
+            emit_cpi!(EpochResolved {
+                config: ctx.accounts.config.key(),
+                epoch: ctx.accounts.epoch_result.epoch,
+                winning_pool_id,
+                winning_weight,
+                total_position_amount: ctx.accounts.epoch_result.total_position_amount,
+                path: ResolutionPath::Admin,
+            });
+        },
+        Settlement::Requested { keeper_tip } => {
+            emit_cpi!(EpochResolutionRequested {
+                config: ctx.accounts.config.key(),
+                epoch: ctx.accounts.epoch_result.epoch,
+                requested_by: ctx.accounts.signer.key(),
+            });
+
+            if keeper_tip > 0 {
+                emit_cpi!(KeeperTipPaid {
+                    config: ctx.accounts.config.key(),
+                    epoch: ctx.accounts.epoch_result.epoch,
+                    keeper: ctx.accounts.signer.key(),
+                    amount: keeper_tip,
+                });
+            }
+        },
+    }
+
+    // settle_epoch moved current_epoch on, next_epoch_result belongs to it
+    let num_pools = ctx.accounts.config.pools_per_epoch;
+    open_epoch(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.next_epoch_result,
+        opening_pools,
+        num_pools,
+        ctx.program_id,
+    )?;
+
+    emit_cpi!(PoolsInitialized {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.next_epoch_result.epoch,
+        pool_count: num_pools,
+        position_price: ctx.accounts.next_epoch_result.position_price,
+        end_at: ctx.accounts.next_epoch_result.end_at,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Crank<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.pools_per_epoch > 0 @ Errors::AutoRolloverDisabled,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + EpochResult::INIT_SPACE,
+        seeds = [
+            SEED_EPOCH_RESULT,
+            config.key().as_ref(),
+            config.current_epoch.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[vrf]
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Rollover<'info> {
+    // Same rule as resolve: Oracle epochs can be rolled over by any keeper
+    #[account(
+        mut,
+        constraint = signer.key() == config.resolver.key()
+            || config.resolution_type == ResolutionType::Oracle @ Errors::UnauthorizedResolver
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.pools_per_epoch > 0 @ Errors::AutoRolloverDisabled,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Paid for the pools of the epoch being resolved, receives their rent back
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_EPOCH_RESULT,
+            config.key().as_ref(),
+            config.current_epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = rent_payer @ Errors::InvalidRentPayer
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + EpochResult::INIT_SPACE,
+        seeds = [
+            SEED_EPOCH_RESULT,
+            config.key().as_ref(),
+            (config.current_epoch + 1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub next_epoch_result: Account<'info, EpochResult>,
+
+    /// CHECK: The oracle queue
+    #[account(mut, address = ephemeral_vrf_sdk::consts::DEFAULT_QUEUE)]
+    pub oracle_queue: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
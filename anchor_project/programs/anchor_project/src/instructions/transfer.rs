@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, UserState, PositionAccount};
+use crate::errors::Errors;
+use crate::events::{ConfigChange, ConfigUpdated, PositionTransferred};
+use crate::instructions::UpdateConfig;
+use crate::instructions::position_nft::PositionNft;
+use crate::constants::{
+    SEED_CONFIG,
+    SEED_POSITION,
+    SEED_USER_STATE,
+    SEED_POSITION_MINT,
+    PAUSE_MINT,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::TokenAccount,
+};
+
+/// Applied immediately, positions are not part of any epoch until committed
+pub fn set_transferable_positions(ctx: Context<UpdateConfig>, transferable_positions: bool) -> Result<()> {
+    ctx.accounts.config.transferable_positions = transferable_positions;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::TransferablePositions { transferable_positions },
+        staged: false,
+    });
+    Ok(())
+}
+
+/// Move a position into the recipient's PDA namespace
+/// The old PositionAccount and its token are closed, a new one is created at the
+/// recipient's next user_index with the same global_id, created_at and price, so the
+/// accumulated weight and the stake move with it. The sender pays for the new accounts
+pub fn transfer_position(ctx: Context<TransferPosition>, _position_id: u64) -> Result<()> {
+    let from_index = ctx.accounts.position.user_index;
+    let to_index = ctx.accounts.recipient_state.position_count;
+    let global_id = ctx.accounts.position.global_id;
+    let created_at = ctx.accounts.position.created_at;
+    let price = ctx.accounts.position.price;
+
+    PositionNft {
+        owner: &ctx.accounts.signer.to_account_info(),
+        config: &ctx.accounts.config,
+        position_mint: &ctx.accounts.position_mint.to_account_info(),
+        position_ata: &ctx.accounts.position_ata.to_account_info(),
+        token_program: &ctx.accounts.position_token_program.to_account_info(),
+    }.burn()?;
+
+    ctx.accounts.new_position.owner = ctx.accounts.recipient.key();
+    ctx.accounts.new_position.user_index = to_index;
+    ctx.accounts.new_position.global_id = global_id;
+    ctx.accounts.new_position.created_at = created_at;
+    ctx.accounts.new_position.price = price;
+
+    ctx.accounts.recipient_state.position_count = ctx.accounts.recipient_state.position_count
+        .checked_add(1)
+        .ok_or(error!(Errors::InvalidAdd))?;
+
+    PositionNft {
+        owner: &ctx.accounts.recipient.to_account_info(),
+        config: &ctx.accounts.config,
+        position_mint: &ctx.accounts.new_position_mint.to_account_info(),
+        position_ata: &ctx.accounts.new_position_ata.to_account_info(),
+        token_program: &ctx.accounts.position_token_program.to_account_info(),
+    }.mint(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.new_position.key(),
+        &ctx.accounts.new_position,
+        &ctx.accounts.associated_token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    emit_cpi!(PositionTransferred {
+        config: ctx.accounts.config.key(),
+        from: ctx.accounts.signer.key(),
+        to: ctx.accounts.recipient.key(),
+        global_id,
+        from_index,
+        to_index,
+        created_at,
+    });
+
+    msg!("Transferred Position - Global ID: {}, User Index: {} -> {}", global_id, from_index, to_index);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(_position_id: u64)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Any wallet can receive a position
+    #[account(
+        constraint = recipient.key() != signer.key() @ Errors::InvalidTransferRecipient
+    )]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.transferable_positions @ Errors::PositionsNotTransferable,
+        // Transfers create positions, so they stop along with minting
+        constraint = !config.is_paused(PAUSE_MINT) @ Errors::MintPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            SEED_POSITION,
+            config.key().as_ref(),
+            signer.key().as_ref(),
+            _position_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub position: Account<'info, PositionAccount>,
+
+    /// CHECK: Position's Token-2022 mint, closed after the burn
+    #[account(
+        mut,
+        seeds = [SEED_POSITION_MINT, position.key().as_ref()],
+        bump
+    )]
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = position_token_program,
+    )]
+    pub position_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [SEED_USER_STATE, config.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_state: Account<'info, UserState>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PositionAccount::INIT_SPACE,
+        seeds = [
+            SEED_POSITION,
+            config.key().as_ref(),
+            recipient.key().as_ref(),
+            recipient_state.position_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub new_position: Account<'info, PositionAccount>,
+
+    /// CHECK: Mint for the re-keyed position, created by the handler
+    #[account(
+        mut,
+        seeds = [SEED_POSITION_MINT, new_position.key().as_ref()],
+        bump
+    )]
+    pub new_position_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient's ATA for new_position_mint, created by the handler
+    #[account(
+        mut,
+        seeds = [
+            recipient.key().as_ref(),
+            position_token_program.key().as_ref(),
+            new_position_mint.key().as_ref()
+        ],
+        seeds::program = associated_token_program.key(),
+        bump
+    )]
+    pub new_position_ata: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
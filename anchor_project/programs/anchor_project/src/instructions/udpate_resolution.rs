@@ -1,27 +1,36 @@
 use anchor_lang::prelude::*;
 use crate::state::{ResolutionType, Config};
 use crate::constants::SEED_CONFIG;
+use crate::errors::Errors;
+use crate::events::{ConfigChange, ConfigUpdated};
 
 pub fn update_resolution_type(
     ctx: Context<UpdateConfig>,
     resolution_type: ResolutionType,
 ) -> Result<()> {
     ctx.accounts.config.resolution_type = resolution_type;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::ResolutionType { resolution_type },
+        staged: false,
+    });
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(
         mut,
-        constraint = config.admin == signer.key()
+        constraint = config.admin == signer.key() @ Errors::UnauthorizedAdmin
     )]
     pub signer: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [SEED_CONFIG],
-        bump
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump
     )]
     pub config: Account<'info, Config>,
 }
\ No newline at end of file
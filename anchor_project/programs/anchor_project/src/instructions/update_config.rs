@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::WeightModel;
+use crate::errors::Errors;
+use crate::events::{ConfigChange, ConfigUpdated};
+use crate::instructions::UpdateConfig;
+use crate::instructions::weight::validate_weight_model;
+
+// @note Updates are staged in config.pending_update and only applied by
+// initialize_pool when the next epoch is opened, so an Active epoch is never repriced.
+
+pub fn update_position_price(
+    ctx: Context<UpdateConfig>,
+    position_price: u64,
+) -> Result<()> {
+    require!(position_price > 0, Errors::InvalidPositionPrice);
+    ctx.accounts.config.pending_update.position_price = Some(position_price);
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::PositionPrice { position_price },
+        staged: true,
+    });
+    Ok(())
+}
+
+pub fn update_epoch_duration(
+    ctx: Context<UpdateConfig>,
+    epoch_duration: i64,
+) -> Result<()> {
+    require!(epoch_duration > 0, Errors::InvalidEpochDuration);
+    ctx.accounts.config.pending_update.epoch_duration = Some(epoch_duration);
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::EpochDuration { epoch_duration },
+        staged: true,
+    });
+    Ok(())
+}
+
+pub fn update_weight_model(
+    ctx: Context<UpdateConfig>,
+    weight_model: WeightModel,
+    weight_rate_numerator: u64,
+    weight_rate_denominator: u64,
+) -> Result<()> {
+    require!(weight_rate_denominator > 0, Errors::InvalidWeightRate);
+    validate_weight_model(&weight_model)?;
+    let pending = &mut ctx.accounts.config.pending_update;
+    pending.weight_model = Some(weight_model);
+    pending.weight_rate_numerator = Some(weight_rate_numerator);
+    pending.weight_rate_denominator = Some(weight_rate_denominator);
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::WeightModel {
+            weight_model,
+            weight_rate_numerator,
+            weight_rate_denominator,
+        },
+        staged: true,
+    });
+    Ok(())
+}
+
+/// Admin-side resolver rotation, e.g. when the resolver key is lost and can't accept a proposal
+/// Staged like the other updates and applied when the next epoch opens
+/// Supersedes an open resolver proposal, the latest admin decision wins
+pub fn update_resolver(
+    ctx: Context<UpdateConfig>,
+    resolver: Pubkey,
+) -> Result<()> {
+    ctx.accounts.config.pending_update.resolver = Some(resolver);
+    ctx.accounts.config.pending_resolver = None;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::Resolver { resolver },
+        staged: true,
+    });
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hashv, sysvar::slot_hashes};
+use crate::state::{Config, EpochResult, EpochResultState, ResolutionPath};
+use crate::errors::Errors;
+use crate::events::{ConfigChange, ConfigUpdated, EpochResolutionRequested, EpochResolved};
+use crate::constants::{SEED_CONFIG, SEED_EPOCH_RESULT, PAUSE_RESOLVE};
+use crate::instructions::UpdateConfig;
+use crate::instructions::resolve::{finalize_epoch, randomness_request_ix};
+use ephemeral_vrf_sdk::anchor::vrf;
+
+// @note Both recovery paths only open once vrf_timeout seconds have passed since the
+// last randomness request, so the oracle always gets the first chance to answer.
+
+/// Applies immediately, it only affects how long Pending epochs wait
+pub fn update_vrf_timeout(ctx: Context<UpdateConfig>, vrf_timeout: i64) -> Result<()> {
+    require!(vrf_timeout > 0, Errors::InvalidVrfTimeout);
+    ctx.accounts.config.vrf_timeout = vrf_timeout;
+    emit_cpi!(ConfigUpdated {
+        config: ctx.accounts.config.key(),
+        updated_by: ctx.accounts.signer.key(),
+        change: ConfigChange::VrfTimeout { vrf_timeout },
+        staged: false,
+    });
+    Ok(())
+}
+
+fn require_vrf_timed_out(config: &Config, epoch_result: &EpochResult) -> Result<i64> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let deadline = epoch_result.requested_at
+        .checked_add(config.vrf_timeout)
+        .ok_or(error!(Errors::InvalidAdd))?;
+    require!(current_time >= deadline, Errors::VrfTimeoutNotReached);
+    Ok(current_time)
+}
+
+/// Ask the oracle again for an epoch still waiting on its callback
+pub fn retry_resolve(ctx: Context<RetryResolve>, _epoch: u64, client_seed: u8) -> Result<()> {
+    let current_time = require_vrf_timed_out(&ctx.accounts.config, &ctx.accounts.epoch_result)?;
+
+    let ix = randomness_request_ix(
+        ctx.accounts.signer.key(),
+        ctx.accounts.oracle_queue.key(),
+        ctx.accounts.epoch_result.key(),
+        ctx.accounts.config.key(),
+        ctx.accounts.event_authority.key(),
+        client_seed,
+    );
+    ctx.accounts
+        .invoke_signed_vrf(&ctx.accounts.signer.to_account_info(), &ix)?;
+
+    ctx.accounts.epoch_result.requested_at = current_time;
+
+    emit_cpi!(EpochResolutionRequested {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.epoch_result.epoch,
+        requested_by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+/// Resolve a timed out epoch from the most recent slot hash
+/// @note Weaker than VRF (the resolver picks the slot it lands in), only meant as a last resort
+pub fn fallback_resolve(ctx: Context<FallbackResolve>, _epoch: u64) -> Result<()> {
+    require_vrf_timed_out(&ctx.accounts.config, &ctx.accounts.epoch_result)?;
+
+    // SlotHashes layout: u64 entry count, then (slot: u64, hash: [u8; 32]) newest first
+    let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 48, Errors::SlotHashesUnavailable);
+    let randomness = hashv(&[
+        &data[16..48],
+        ctx.accounts.epoch_result.key().as_ref(),
+    ]).to_bytes();
+    drop(data);
+
+    // An epoch without pools has no winner to pick, it can only be cancelled
+    let max_pool_id = ctx.accounts.epoch_result.pool_count
+        .checked_sub(1)
+        .ok_or(error!(Errors::NoPoolsProvided))?;
+    let rnd_u8 = ephemeral_vrf_sdk::rnd::random_u8_with_range(
+        &randomness,
+         0,
+         max_pool_id
+    );
+    let winning_weight = finalize_epoch(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch_result,
+        rnd_u8,
+        ResolutionPath::SlotHashFallback,
+    )?;
+
+    emit_cpi!(EpochResolved {
+        config: ctx.accounts.config.key(),
+        epoch: ctx.accounts.epoch_result.epoch,
+        winning_pool_id: rnd_u8,
+        winning_weight,
+        total_position_amount: ctx.accounts.epoch_result.total_position_amount,
+        path: ResolutionPath::SlotHashFallback,
+    });
+    Ok(())
+}
+
+#[vrf]
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct RetryResolve<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == config.resolver.key()
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_result.epoch_result_state == EpochResultState::Pending @ Errors::EpochNotPending
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    /// CHECK: The oracle queue
+    #[account(mut, address = ephemeral_vrf_sdk::consts::DEFAULT_QUEUE)]
+    pub oracle_queue: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct FallbackResolve<'info> {
+    #[account(
+        constraint = signer.key() == config.resolver.key()
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG, config.instance_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_RESOLVE) @ Errors::ResolvePaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_EPOCH_RESULT, config.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump,
+        constraint = epoch_result.epoch_result_state == EpochResultState::Pending @ Errors::EpochNotPending
+    )]
+    pub epoch_result: Account<'info, EpochResult>,
+
+    /// CHECK: SlotHashes sysvar, read manually since it is too large to deserialize
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
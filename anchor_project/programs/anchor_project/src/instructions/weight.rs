@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::WeightModel;
+use crate::state::{WeightModel, WeightTier};
 use crate::errors::Errors;
 
 /// Precision multiplier for weight calculations
@@ -7,6 +7,140 @@ use crate::errors::Errors;
 /// This allows for sub-second precision while using integer math
 pub const WEIGHT_PRECISION: u64 = 10_000;
 
+/// Highest Capped max_weight (1,000,000.0), pool weights are u64 sums of position weights
+pub const MAX_CAPPED_WEIGHT: u64 = 1_000_000 * WEIGHT_PRECISION;
+
+/// Fractional bits computed by log2_scaled
+const LOG2_FRACTION_BITS: u32 = 20;
+
+/// Position age in seconds, rejecting positions from the future
+fn position_age(created_at: i64, current_time: i64) -> Result<u64> {
+    let age = current_time
+        .checked_sub(created_at)
+        .ok_or(error!(Errors::InvalidCalculation))?;
+
+    if age < 0 {
+        return err!(Errors::InvalidCalculation);
+    }
+    Ok(age as u64)
+}
+
+/// Linear weight (age * numerator / denominator) scaled by WEIGHT_PRECISION, in u128
+/// so the curved models can take extreme ages before flattening them
+fn linear_weight_scaled(
+    age: u64,
+    weight_rate_numerator: u64,
+    weight_rate_denominator: u64,
+) -> Result<u128> {
+    if weight_rate_denominator == 0 {
+        return err!(Errors::InvalidCalculation);
+    }
+    (age as u128)
+        .checked_mul(weight_rate_numerator as u128)
+        .ok_or(error!(Errors::InvalidMul))?
+        .checked_mul(WEIGHT_PRECISION as u128)
+        .ok_or(error!(Errors::InvalidMul))?
+        .checked_div(weight_rate_denominator as u128)
+        .ok_or(error!(Errors::InvalidDiv))
+}
+
+/// Integer square root (floor) by Newton's method
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// log2(value / WEIGHT_PRECISION) scaled by WEIGHT_PRECISION, for value >= WEIGHT_PRECISION
+/// Integer part by halving, fractional part bit by bit by repeated squaring
+fn log2_scaled(value: u128) -> u128 {
+    let precision = WEIGHT_PRECISION as u128;
+    let mut integer_part: u128 = 0;
+    let mut y = value;
+    while y >= 2 * precision {
+        y /= 2;
+        integer_part += 1;
+    }
+
+    // y / precision is now in [1, 2), square it in a finer fixed point
+    const ONE: u128 = 1 << 60;
+    let mut z = y * ONE / precision;
+    let mut fraction: u128 = 0;
+    for _ in 0..LOG2_FRACTION_BITS {
+        z = z * z / ONE;
+        fraction <<= 1;
+        if z >= 2 * ONE {
+            z /= 2;
+            fraction |= 1;
+        }
+    }
+
+    integer_part * precision + ((fraction * precision) >> LOG2_FRACTION_BITS)
+}
+
+/// peak * 2^(-elapsed / half_life), exact at every whole half-life and linear in between
+fn decay_scaled(peak: u128, elapsed: u64, half_life: u64) -> Result<u128> {
+    let halvings = elapsed / half_life;
+    if halvings >= u128::BITS as u64 {
+        return Ok(0);
+    }
+    let start = peak >> halvings;
+    let remainder = (elapsed % half_life) as u128;
+    // Between start and start / 2, so drop up to half of start
+    let dropped = start
+        .checked_mul(remainder)
+        .ok_or(error!(Errors::InvalidMul))?
+        .checked_div(2 * half_life as u128)
+        .ok_or(error!(Errors::InvalidDiv))?;
+    start
+        .checked_sub(dropped)
+        .ok_or(error!(Errors::InvalidCalculation))
+}
+
+/// Check the parameters of a weight model before it is stored in Config
+/// Capped needs a cap between 1.0 and MAX_CAPPED_WEIGHT, Tiered needs its used tiers
+/// (weight > 0) first, with strictly increasing min_age and weights of at least 1.0,
+/// RiseAndDecay needs a non-negative peak_age and a positive half_life
+pub fn validate_weight_model(model: &WeightModel) -> Result<()> {
+    match model {
+        WeightModel::Capped { max_weight } => {
+            require!(
+                (WEIGHT_PRECISION..=MAX_CAPPED_WEIGHT).contains(max_weight),
+                Errors::InvalidWeightModel
+            );
+        },
+        WeightModel::Tiered { breakpoints } => {
+            let used = breakpoints.iter().take_while(|tier| tier.weight > 0).count();
+            require!(used > 0, Errors::InvalidWeightModel);
+            require!(
+                breakpoints[used..].iter().all(|tier| *tier == WeightTier::default()),
+                Errors::InvalidWeightModel
+            );
+            require!(breakpoints[0].min_age >= 0, Errors::InvalidWeightModel);
+            require!(
+                breakpoints[..used].iter().all(|tier| tier.weight >= WEIGHT_PRECISION),
+                Errors::InvalidWeightModel
+            );
+            require!(
+                breakpoints[..used].windows(2).all(|pair| pair[0].min_age < pair[1].min_age),
+                Errors::InvalidWeightModel
+            );
+        },
+        WeightModel::RiseAndDecay { peak_age, half_life } => {
+            require!(*peak_age >= 0 && *half_life > 0, Errors::InvalidWeightModel);
+        },
+        _ => {},
+    }
+    Ok(())
+}
+
 /// Calculate weight based on the configured weight model
 /// Returns weight scaled by WEIGHT_PRECISION (10,000)
 pub fn calculate_weight(
@@ -66,5 +200,249 @@ pub fn calculate_weight(
 
             Ok(weight)
         },
+
+        WeightModel::Sqrt => {
+            // weight = sqrt(linear), with linear the TimeBased weight
+            // In fixed point: sqrt(l / P) * P = sqrt(l * P)
+            //   - linear 4.0 -> 2.0, linear 100.0 -> 10.0, linear 10,000.0 -> 100.0
+            let age = position_age(created_at, current_time)?;
+            let linear = linear_weight_scaled(age, weight_rate_numerator, weight_rate_denominator)?;
+            let scaled = linear
+                .checked_mul(WEIGHT_PRECISION as u128)
+                .ok_or(error!(Errors::InvalidMul))?;
+            let weight: u64 = isqrt(scaled)
+                .try_into()
+                .map_err(|_| error!(Errors::InvalidMul))?;
+
+            Ok(weight.max(WEIGHT_PRECISION))
+        },
+
+        WeightModel::Log2 => {
+            // weight = 1 + log2(1 + linear), with linear the TimeBased weight
+            //   - linear 0.0 -> 1.0, linear 1.0 -> 2.0, linear 7.0 -> 4.0, linear 1023.0 -> 11.0
+            let age = position_age(created_at, current_time)?;
+            let linear = linear_weight_scaled(age, weight_rate_numerator, weight_rate_denominator)?;
+            let one_plus_linear = linear
+                .checked_add(WEIGHT_PRECISION as u128)
+                .ok_or(error!(Errors::InvalidAdd))?;
+            let weight: u64 = log2_scaled(one_plus_linear)
+                .checked_add(WEIGHT_PRECISION as u128)
+                .ok_or(error!(Errors::InvalidAdd))?
+                .try_into()
+                .map_err(|_| error!(Errors::InvalidMul))?;
+
+            Ok(weight.max(WEIGHT_PRECISION))
+        },
+
+        WeightModel::Capped { max_weight } => {
+            // TimeBased growth until max_weight, i.e. until age = max_weight / rate
+            let age = position_age(created_at, current_time)?;
+            let linear = linear_weight_scaled(age, weight_rate_numerator, weight_rate_denominator)?;
+            let weight = linear.min(max_weight as u128) as u64;
+
+            Ok(weight.max(WEIGHT_PRECISION))
+        },
+
+        WeightModel::Tiered { breakpoints } => {
+            // Weight of the last used tier the position has reached
+            //   e.g. [(0, 1.0), (1 week, 2.0), (30 days, 3.0)]
+            let age = position_age(created_at, current_time)?;
+            let weight = breakpoints
+                .iter()
+                .take_while(|tier| tier.weight > 0)
+                .filter(|tier| age >= tier.min_age as u64)
+                .last()
+                .map_or(WEIGHT_PRECISION, |tier| tier.weight);
+
+            Ok(weight.max(WEIGHT_PRECISION))
+        },
+
+        WeightModel::RiseAndDecay { peak_age, half_life } => {
+            // TimeBased growth until peak_age, then the peak weight halves every half_life
+            //   e.g. rate 1/s, peak 100s, half-life 50s: age 100 -> 100.0, 150 -> 50.0, 200 -> 25.0
+            if peak_age < 0 || half_life <= 0 {
+                return err!(Errors::InvalidWeightModel);
+            }
+            let age = position_age(created_at, current_time)?;
+            let peak_age = peak_age as u64;
+            let weight = if age <= peak_age {
+                linear_weight_scaled(age, weight_rate_numerator, weight_rate_denominator)?
+            } else {
+                let peak = linear_weight_scaled(peak_age, weight_rate_numerator, weight_rate_denominator)?;
+                decay_scaled(peak, age - peak_age, half_life as u64)?
+            };
+            let weight = weight.min(u64::MAX as u128) as u64;
+
+            Ok(weight.max(WEIGHT_PRECISION))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MAX_WEIGHT_TIERS;
+
+    const DAY: i64 = 86_400;
+
+    fn tiers(used: &[(i64, u64)]) -> [WeightTier; MAX_WEIGHT_TIERS] {
+        let mut breakpoints = [WeightTier::default(); MAX_WEIGHT_TIERS];
+        for (tier, (min_age, weight)) in breakpoints.iter_mut().zip(used) {
+            *tier = WeightTier { min_age: *min_age, weight: *weight };
+        }
+        breakpoints
+    }
+
+    fn weight(model: WeightModel, age: i64, numerator: u64, denominator: u64) -> Result<u64> {
+        calculate_weight(model, 0, age, numerator, denominator)
+    }
+
+    #[test]
+    fn sqrt_follows_square_root_of_linear_weight() {
+        assert_eq!(weight(WeightModel::Sqrt, 4, 1, 1).unwrap(), 2 * WEIGHT_PRECISION);
+        assert_eq!(weight(WeightModel::Sqrt, 100, 1, 1).unwrap(), 10 * WEIGHT_PRECISION);
+        assert_eq!(weight(WeightModel::Sqrt, 10_000, 1, 1).unwrap(), 100 * WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn log2_follows_log_of_linear_weight() {
+        assert_eq!(weight(WeightModel::Log2, 1, 1, 1).unwrap(), 2 * WEIGHT_PRECISION);
+        assert_eq!(weight(WeightModel::Log2, 7, 1, 1).unwrap(), 4 * WEIGHT_PRECISION);
+        assert_eq!(weight(WeightModel::Log2, 1023, 1, 1).unwrap(), 11 * WEIGHT_PRECISION);
+        // log2(3) = 1.58496...
+        assert_eq!(weight(WeightModel::Log2, 2, 1, 1).unwrap(), 25_849);
+    }
+
+    #[test]
+    fn curved_models_keep_minimum_weight() {
+        for model in [WeightModel::Sqrt, WeightModel::Log2] {
+            assert_eq!(weight(model, 0, 1, 1).unwrap(), WEIGHT_PRECISION);
+        }
+        // sqrt(0.001) is clamped up to 1.0
+        assert_eq!(weight(WeightModel::Sqrt, 1, 1, 1_000).unwrap(), WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn curved_models_flatten_old_positions() {
+        // A month old position is ~30x a day old one under TimeBased, far less here
+        for model in [WeightModel::Sqrt, WeightModel::Log2] {
+            let day = weight(model, DAY, 1, 3_600).unwrap();
+            let month = weight(model, 30 * DAY, 1, 3_600).unwrap();
+            assert!(month > day);
+            assert!(month < 6 * day);
+        }
+    }
+
+    #[test]
+    fn curved_models_handle_extreme_ages() {
+        // TimeBased overflows u64 here, the curved models stay in range
+        assert!(weight(WeightModel::TimeBased, i64::MAX, 1, 1).is_err());
+        assert_eq!(weight(WeightModel::Sqrt, i64::MAX, 1, 1).unwrap(), 30_370_004_999_760);
+        let log2 = weight(WeightModel::Log2, i64::MAX, 1, 1).unwrap();
+        assert!(log2 > 63 * WEIGHT_PRECISION && log2 <= 65 * WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn curved_models_error_instead_of_overflowing() {
+        for model in [WeightModel::Sqrt, WeightModel::Log2] {
+            assert!(weight(model, i64::MAX, u64::MAX, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn curved_models_reject_invalid_inputs() {
+        for model in [WeightModel::Sqrt, WeightModel::Log2] {
+            assert!(weight(model, 10, 1, 0).is_err());
+            assert!(calculate_weight(model, 10, 0, 1, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn capped_stops_growing_at_max_weight() {
+        let model = WeightModel::Capped { max_weight: 5 * WEIGHT_PRECISION };
+        assert_eq!(weight(model, 0, 1, 1).unwrap(), WEIGHT_PRECISION);
+        assert_eq!(weight(model, 3, 1, 1).unwrap(), 3 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 5, 1, 1).unwrap(), 5 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, i64::MAX, 1, 1).unwrap(), 5 * WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn tiered_uses_last_reached_breakpoint() {
+        let model = WeightModel::Tiered {
+            breakpoints: tiers(&[
+                (0, WEIGHT_PRECISION),
+                (7 * DAY, 2 * WEIGHT_PRECISION),
+                (30 * DAY, 3 * WEIGHT_PRECISION),
+            ]),
+        };
+        assert_eq!(weight(model, DAY, 1, 1).unwrap(), WEIGHT_PRECISION);
+        assert_eq!(weight(model, 7 * DAY, 1, 1).unwrap(), 2 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 29 * DAY, 1, 1).unwrap(), 2 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, i64::MAX, 1, 1).unwrap(), 3 * WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn tiered_defaults_to_minimum_before_first_breakpoint() {
+        let model = WeightModel::Tiered { breakpoints: tiers(&[(DAY, 2 * WEIGHT_PRECISION)]) };
+        assert_eq!(weight(model, DAY - 1, 1, 1).unwrap(), WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn validates_capped_and_tiered_parameters() {
+        assert!(validate_weight_model(&WeightModel::Capped { max_weight: WEIGHT_PRECISION }).is_ok());
+        assert!(validate_weight_model(&WeightModel::Capped { max_weight: WEIGHT_PRECISION - 1 }).is_err());
+        assert!(validate_weight_model(&WeightModel::Capped { max_weight: MAX_CAPPED_WEIGHT }).is_ok());
+        assert!(validate_weight_model(&WeightModel::Capped { max_weight: MAX_CAPPED_WEIGHT + 1 }).is_err());
+        assert!(validate_weight_model(&WeightModel::Capped { max_weight: u64::MAX }).is_err());
+
+        let valid = tiers(&[(0, WEIGHT_PRECISION), (DAY, 2 * WEIGHT_PRECISION)]);
+        assert!(validate_weight_model(&WeightModel::Tiered { breakpoints: valid }).is_ok());
+
+        // No tiers, unsorted ages, sub 1.0 weight, negative age, gap before a used tier
+        let invalid = [
+            tiers(&[]),
+            tiers(&[(DAY, WEIGHT_PRECISION), (0, 2 * WEIGHT_PRECISION)]),
+            tiers(&[(0, WEIGHT_PRECISION - 1)]),
+            tiers(&[(-1, WEIGHT_PRECISION)]),
+            tiers(&[(0, WEIGHT_PRECISION), (DAY, 0), (2 * DAY, 2 * WEIGHT_PRECISION)]),
+        ];
+        for breakpoints in invalid {
+            assert!(validate_weight_model(&WeightModel::Tiered { breakpoints }).is_err());
+        }
+    }
+
+    #[test]
+    fn rise_and_decay_peaks_then_halves() {
+        let model = WeightModel::RiseAndDecay { peak_age: 100, half_life: 50 };
+        assert_eq!(weight(model, 50, 1, 1).unwrap(), 50 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 100, 1, 1).unwrap(), 100 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 125, 1, 1).unwrap(), 75 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 150, 1, 1).unwrap(), 50 * WEIGHT_PRECISION);
+        assert_eq!(weight(model, 200, 1, 1).unwrap(), 25 * WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn rise_and_decay_never_drops_below_minimum() {
+        let model = WeightModel::RiseAndDecay { peak_age: 100, half_life: 1 };
+        assert_eq!(weight(model, 10_000, 1, 1).unwrap(), WEIGHT_PRECISION);
+        assert_eq!(weight(model, i64::MAX, 1, 1).unwrap(), WEIGHT_PRECISION);
+    }
+
+    #[test]
+    fn rise_and_decay_handles_large_parameters() {
+        // Half-life so long the weight has barely moved off its peak
+        let model = WeightModel::RiseAndDecay { peak_age: 1_000_000_000, half_life: i64::MAX };
+        assert_eq!(weight(model, 2_000_000_000, 1, 1).unwrap(), 9_999_999_999_458);
+
+        // peak * elapsed no longer fits in u128
+        let model = WeightModel::RiseAndDecay { peak_age: 1_000_000_000_000_000, half_life: i64::MAX };
+        assert!(weight(model, 1_000_000_000_001_000, u64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn validates_rise_and_decay_parameters() {
+        assert!(validate_weight_model(&WeightModel::RiseAndDecay { peak_age: 0, half_life: 1 }).is_ok());
+        assert!(validate_weight_model(&WeightModel::RiseAndDecay { peak_age: -1, half_life: 1 }).is_err());
+        assert!(validate_weight_model(&WeightModel::RiseAndDecay { peak_age: 100, half_life: 0 }).is_err());
     }
 }
@@ -4,6 +4,7 @@ pub mod state;
 pub mod instructions;
 pub mod errors;
 pub mod constants;
+pub mod events;
 
 use instructions::*;
 use state::*;
@@ -14,14 +15,19 @@ declare_id!("BUgW4g2BkcZVg1eNNXhkYDCCPomdWWeNnrUPQgZzsnDT");
 pub mod anchor_project {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, weight_model: WeightModel, resolution_type: ResolutionType, resolver: Pubkey, epoch_duration: i64, weight_rate_numerator: u64, weight_rate_denominator: u64) -> Result<()> {
-        crate::instructions::initialize(ctx, weight_model, resolution_type, resolver, epoch_duration, weight_rate_numerator, weight_rate_denominator)
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(ctx: Context<Initialize>, weight_model: WeightModel, resolution_type: ResolutionType, resolver: Pubkey, epoch_duration: i64, position_price: u64, weight_rate_numerator: u64, weight_rate_denominator: u64, instance_id: u64) -> Result<()> {
+        crate::instructions::initialize(ctx, weight_model, resolution_type, resolver, epoch_duration, position_price, weight_rate_numerator, weight_rate_denominator, instance_id)
     }
 
     pub fn mint_position(ctx: Context<MintPosition>) -> Result<()> {
         crate::instructions::mint_position(ctx)
     }
 
+    pub fn mint_positions<'info>(ctx: Context<'_, '_, '_, 'info, MintPositions<'info>>, count: u8) -> Result<()> {
+        crate::instructions::mint_positions(ctx, count)
+    }
+
     pub fn initialize_pool<'info>(ctx: Context<'_, '_, '_, 'info, StartContext<'info>>, num_pools: u8) -> Result<()> {
         crate::instructions::initialize_pool(ctx, num_pools)
     }
@@ -30,6 +36,10 @@ pub mod anchor_project {
         crate::instructions::commit(ctx, position_i, pool_id)
     }
 
+    pub fn commit_many<'info>(ctx: Context<'_, '_, 'info, 'info, CommitMany<'info>>, pool_id: u8) -> Result<()> {
+        crate::instructions::commit_many(ctx, pool_id)
+    }
+
     pub fn resolve(ctx: Context<ResolveContext>, winning_pool_id: u8) -> Result<()> {
         crate::instructions::resolve(ctx, winning_pool_id)
     }
@@ -38,6 +48,10 @@ pub mod anchor_project {
         crate::instructions::claim(ctx, pool_id, epoch)
     }
 
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+        crate::instructions::claim_many(ctx)
+    }
+
     pub fn callback_resolve(ctx: Context<CallbackResolve>, randomness: [u8; 32],) -> Result<()> {
         crate::instructions::callback_resolve(ctx, randomness)
     }
@@ -45,5 +59,117 @@ pub mod anchor_project {
     pub fn update_resolution_type(ctx: Context<UpdateConfig>, resolution_type: ResolutionType) -> Result<()> {
         crate::instructions::update_resolution_type(ctx, resolution_type)
     }
+
+    pub fn update_position_price(ctx: Context<UpdateConfig>, position_price: u64) -> Result<()> {
+        crate::instructions::update_position_price(ctx, position_price)
+    }
+
+    pub fn update_epoch_duration(ctx: Context<UpdateConfig>, epoch_duration: i64) -> Result<()> {
+        crate::instructions::update_epoch_duration(ctx, epoch_duration)
+    }
+
+    pub fn update_weight_model(ctx: Context<UpdateConfig>, weight_model: WeightModel, weight_rate_numerator: u64, weight_rate_denominator: u64) -> Result<()> {
+        crate::instructions::update_weight_model(ctx, weight_model, weight_rate_numerator, weight_rate_denominator)
+    }
+
+    pub fn update_resolver(ctx: Context<UpdateConfig>, resolver: Pubkey) -> Result<()> {
+        crate::instructions::update_resolver(ctx, resolver)
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        crate::instructions::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        crate::instructions::accept_admin(ctx)
+    }
+
+    pub fn cancel_admin_proposal(ctx: Context<UpdateConfig>) -> Result<()> {
+        crate::instructions::cancel_admin_proposal(ctx)
+    }
+
+    pub fn propose_resolver(ctx: Context<UpdateConfig>, new_resolver: Pubkey) -> Result<()> {
+        crate::instructions::propose_resolver(ctx, new_resolver)
+    }
+
+    pub fn accept_resolver(ctx: Context<AcceptResolver>) -> Result<()> {
+        crate::instructions::accept_resolver(ctx)
+    }
+
+    pub fn cancel_resolver_proposal(ctx: Context<UpdateConfig>) -> Result<()> {
+        crate::instructions::cancel_resolver_proposal(ctx)
+    }
+
+    pub fn pause(ctx: Context<PauseContext>, flags: u8) -> Result<()> {
+        crate::instructions::pause(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<UpdateConfig>, flags: u8) -> Result<()> {
+        crate::instructions::unpause(ctx, flags)
+    }
+
+    pub fn set_guardian(ctx: Context<UpdateConfig>, guardian: Option<Pubkey>) -> Result<()> {
+        crate::instructions::set_guardian(ctx, guardian)
+    }
+
+    pub fn update_fees(ctx: Context<UpdateConfig>, mint_fee_bps: u16, claim_fee_bps: u16) -> Result<()> {
+        crate::instructions::update_fees(ctx, mint_fee_bps, claim_fee_bps)
+    }
+
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>) -> Result<()> {
+        crate::instructions::set_fee_recipient(ctx)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        crate::instructions::withdraw_fees(ctx)
+    }
+
+    pub fn cancel_epoch<'info>(ctx: Context<'_, '_, '_, 'info, CancelEpoch<'info>>, epoch: u64) -> Result<()> {
+        crate::instructions::cancel_epoch(ctx, epoch)
+    }
+
+    pub fn refund(ctx: Context<RefundContext>, pool_id: u8, epoch: u64) -> Result<()> {
+        crate::instructions::refund(ctx, pool_id, epoch)
+    }
+
+    pub fn update_vrf_timeout(ctx: Context<UpdateConfig>, vrf_timeout: i64) -> Result<()> {
+        crate::instructions::update_vrf_timeout(ctx, vrf_timeout)
+    }
+
+    pub fn retry_resolve(ctx: Context<RetryResolve>, epoch: u64, client_seed: u8) -> Result<()> {
+        crate::instructions::retry_resolve(ctx, epoch, client_seed)
+    }
+
+    pub fn fallback_resolve(ctx: Context<FallbackResolve>, epoch: u64) -> Result<()> {
+        crate::instructions::fallback_resolve(ctx, epoch)
+    }
+
+    pub fn update_pools_per_epoch(ctx: Context<UpdateConfig>, pools_per_epoch: u8) -> Result<()> {
+        crate::instructions::update_pools_per_epoch(ctx, pools_per_epoch)
+    }
+
+    pub fn update_keeper_tip(ctx: Context<UpdateConfig>, keeper_tip: u64) -> Result<()> {
+        crate::instructions::update_keeper_tip(ctx, keeper_tip)
+    }
+
+    pub fn crank<'info>(ctx: Context<'_, '_, '_, 'info, Crank<'info>>) -> Result<()> {
+        crate::instructions::crank(ctx)
+    }
+
+    pub fn rollover<'info>(ctx: Context<'_, '_, '_, 'info, Rollover<'info>>, client_seed: u8) -> Result<()> {
+        crate::instructions::rollover(ctx, client_seed)
+    }
+
+    pub fn preview_weight<'info>(ctx: Context<'_, '_, '_, 'info, PreviewWeight<'info>>) -> Result<WeightPreview> {
+        crate::instructions::preview_weight(ctx)
+    }
+
+    pub fn set_transferable_positions(ctx: Context<UpdateConfig>, transferable_positions: bool) -> Result<()> {
+        crate::instructions::set_transferable_positions(ctx, transferable_positions)
+    }
+
+    pub fn transfer_position(ctx: Context<TransferPosition>, position_id: u64) -> Result<()> {
+        crate::instructions::transfer_position(ctx, position_id)
+    }
     
 }
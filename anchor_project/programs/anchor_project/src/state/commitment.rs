@@ -8,4 +8,6 @@ pub struct Commitment {
     pub weight: u64,
     pub pool_id: u8,
     pub epoch: u64,
+    // Sum of the prices paid for the committed positions
+    pub position_value: u64,
 }
\ No newline at end of file
@@ -1,9 +1,38 @@
 use anchor_lang::prelude::*;
+use crate::constants::MAX_WEIGHT_TIERS;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
 pub enum WeightModel {
     Constant,      // weight = 1
     TimeBased,     // weight = age (current_time - created_at)
+    Sqrt,          // weight = sqrt(age), diminishing returns
+    Log2,          // weight = 1 + log2(1 + age), strongly diminishing returns
+    Capped { max_weight: u64 },    // TimeBased, stops growing at max_weight (scaled)
+    Tiered { breakpoints: [WeightTier; MAX_WEIGHT_TIERS] }, // step function of age
+    RiseAndDecay { peak_age: i64, half_life: i64 }, // TimeBased until peak_age, then halves every half_life seconds
+}
+
+impl WeightModel {
+    /// Short name, shown in position NFT metadata
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeightModel::Constant => "Constant",
+            WeightModel::TimeBased => "TimeBased",
+            WeightModel::Sqrt => "Sqrt",
+            WeightModel::Log2 => "Log2",
+            WeightModel::Capped { .. } => "Capped",
+            WeightModel::Tiered { .. } => "Tiered",
+            WeightModel::RiseAndDecay { .. } => "RiseAndDecay",
+        }
+    }
+}
+
+/// Positions at least `min_age` seconds old get `weight` (scaled)
+/// Unused trailing tiers are left zeroed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct WeightTier {
+    pub min_age: i64,
+    pub weight: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
@@ -18,10 +47,15 @@ pub enum ResolutionType {
 pub struct Config {
     pub admin: Pubkey,
     pub resolver: Pubkey,
+    // Two-step handover, set by the admin and accepted by the new key
+    pub pending_admin: Option<Pubkey>,
+    pub pending_resolver: Option<Pubkey>,
     pub current_epoch: u64,
     pub total_positions_minted: u64,
     pub position_price: u64,
     pub remaining_total_position: u64,
+    // Value of the positions above, paid out by the next epoch that has a winner
+    pub remaining_total_value: u64,
     pub allowed_mint: Pubkey,
     pub treasury_ata: Pubkey,
 
@@ -35,4 +69,84 @@ pub struct Config {
     // Example: 10 weight per 5 minutes = numerator: 10, denominator: 300
     pub weight_rate_numerator: u64,
     pub weight_rate_denominator: u64,
+
+    // Admin updates staged until the next epoch is opened
+    pub pending_update: PendingConfigUpdate,
+
+    // Instance namespace: every other PDA is seeded with this config's key
+    pub instance_id: u64,
+    pub bump: u8,
+
+    // Emergency switches, bitmask of PAUSE_* flags
+    // The guardian can only pause, the admin can pause and unpause
+    pub paused: u8,
+    pub guardian: Option<Pubkey>,
+
+    // Protocol fees, held in the treasury until withdrawn to fee_recipient_ata
+    // mint fee is paid on top of position_price, claim fee is taken from rewards
+    pub mint_fee_bps: u16,
+    pub claim_fee_bps: u16,
+    pub fee_recipient_ata: Pubkey,
+    pub accrued_fees: u64,
+
+    // Seconds a Pending epoch waits for the VRF callback before a retry or fallback
+    pub vrf_timeout: i64,
+
+    // Pools opened by the permissionless crank, 0 disables automatic rollover
+    pub pools_per_epoch: u8,
+
+    // Lamports paid out of reclaimed pool rent to a keeper resolving an Oracle epoch
+    pub keeper_tip: u64,
+
+    // Secondary market mode, lets owners hand positions over with transfer_position
+    pub transferable_positions: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct PendingConfigUpdate {
+    pub position_price: Option<u64>,
+    pub epoch_duration: Option<i64>,
+    pub weight_model: Option<WeightModel>,
+    pub weight_rate_numerator: Option<u64>,
+    pub weight_rate_denominator: Option<u64>,
+    pub mint_fee_bps: Option<u16>,
+    pub claim_fee_bps: Option<u16>,
+    pub resolver: Option<Pubkey>,
+}
+
+impl Config {
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// Apply staged admin updates, called when a new epoch is opened
+    /// so values never change while an EpochResult is Active
+    pub fn apply_pending_update(&mut self) {
+        let pending = self.pending_update;
+        if let Some(position_price) = pending.position_price {
+            self.position_price = position_price;
+        }
+        if let Some(epoch_duration) = pending.epoch_duration {
+            self.epoch_duration = epoch_duration;
+        }
+        if let Some(weight_model) = pending.weight_model {
+            self.weight_model = weight_model;
+        }
+        if let Some(weight_rate_numerator) = pending.weight_rate_numerator {
+            self.weight_rate_numerator = weight_rate_numerator;
+        }
+        if let Some(weight_rate_denominator) = pending.weight_rate_denominator {
+            self.weight_rate_denominator = weight_rate_denominator;
+        }
+        if let Some(mint_fee_bps) = pending.mint_fee_bps {
+            self.mint_fee_bps = mint_fee_bps;
+        }
+        if let Some(claim_fee_bps) = pending.claim_fee_bps {
+            self.claim_fee_bps = claim_fee_bps;
+        }
+        if let Some(resolver) = pending.resolver {
+            self.resolver = resolver;
+        }
+        self.pending_update = PendingConfigUpdate::default();
+    }
 }
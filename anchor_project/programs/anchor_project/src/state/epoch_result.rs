@@ -13,6 +13,20 @@ pub struct EpochResult {
     pub winning_pool_id: u8,
     pub epoch_result_state: EpochResultState,
     pub pool_count: u8,
+    // Position price snapshot taken when the epoch is opened
+    pub position_price: u64,
+    // Claim fee snapshot taken when the epoch is opened
+    pub claim_fee_bps: u16,
+    // Positions carried over from unclaimed epochs, restored if this epoch is cancelled
+    pub carried_over_positions: u64,
+    // Tokens the winners split: value committed to the pools plus the carried over value
+    pub total_value: u64,
+    pub carried_over_value: u64,
+    // When randomness was last requested, used for the VRF timeout
+    pub requested_at: i64,
+    pub resolution_path: ResolutionPath,
+    // Paid for the pools when the epoch was opened, their rent goes back to it
+    pub rent_payer: Pubkey,
     #[max_len(MAX_POOLS_PER_INIT)]
     pub pool_weights: [u64; MAX_POOLS_PER_INIT as usize],
 }
@@ -22,4 +36,13 @@ pub enum EpochResultState {
     Active,
     Pending,
     Resolved,
-}
\ No newline at end of file
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ResolutionPath {
+    Unresolved,
+    Admin,
+    Vrf,
+    SlotHashFallback,
+}
@@ -7,4 +7,6 @@ pub struct Pool {
     pub total_weight: u64,
     pub id: u8,
     pub epoch: u64,
+    // Sum of the prices paid for the committed positions
+    pub total_value: u64,
 }
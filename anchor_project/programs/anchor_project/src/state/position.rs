@@ -7,6 +7,8 @@ pub struct PositionAccount {
     pub user_index: u64,
     pub global_id: u64,
     pub created_at: i64,
+    // Price paid for the position, without the mint fee
+    pub price: u64,
 }
 
 #[account]
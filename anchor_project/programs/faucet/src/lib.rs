@@ -1,57 +1,230 @@
-use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022::{MintTo, TransferChecked, Token2022, mint_to, transfer_checked},
+    token_2022::{
+        CloseAccount, MintTo, SetAuthority, TransferChecked, Token2022,
+        close_account, mint_to, set_authority, transfer_checked,
+        spl_token_2022::instruction::AuthorityType,
+    },
     token_interface::{Mint, TokenAccount},
 };
 
 declare_id!("JAewuQVbK92ead6kfgDxxkqFeQhwLxPLjihVbYCgowPs");
 
 const DEFAULT_CLAIM_AMOUNT: u64 = 10_000;
+const DEFAULT_CLAIM_COOLDOWN: i64 = 86_400;
+
+/// Whole tokens to raw units of a mint with `decimals`
+fn to_raw_amount(amount: u64, decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| unit.checked_mul(amount))
+        .ok_or(error!(FaucetError::ArithmeticOverflow))
+}
+
+/// Allowlist leaf for `wallet` allowed `amount` raw tokens
+/// Leaves and nodes are domain separated so a node can never pass as a leaf
+fn allowlist_leaf(wallet: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[&[0], wallet.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Walk a merkle proof up to the root, sibling pairs are hashed in sorted order
+fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[&[1], &left, &right]).to_bytes()
+    });
+    computed == *root
+}
 
 #[program]
 pub mod faucet {
     use super::*;
 
-    pub fn initialize(ctx: Context<InitializeContext>, initial_supply: u64, claim_amount: Option<u64>) -> Result<()> {
+    /// Create faucet `faucet_id` with its own mint of `decimals` decimals
+    /// `claim_cooldown` is in seconds, `lifetime_cap` in whole tokens like `claim_amount`
+    pub fn initialize(
+        ctx: Context<InitializeContext>,
+        faucet_id: u64,
+        decimals: u8,
+        initial_supply: u64,
+        claim_amount: Option<u64>,
+        claim_cooldown: Option<i64>,
+        lifetime_cap: Option<u64>,
+    ) -> Result<()> {
         ctx.accounts.config.admin = ctx.accounts.signer.key();
+        ctx.accounts.config.faucet_id = faucet_id;
+        ctx.accounts.config.decimals = decimals;
 
         let claim_amount_ui = claim_amount.unwrap_or(DEFAULT_CLAIM_AMOUNT);
-        let claim_amount_raw = LAMPORTS_PER_SOL
-            .checked_mul(claim_amount_ui)
-            .ok_or(error!(FaucetError::ArithmeticOverflow))?;
-        ctx.accounts.config.claim_amount = claim_amount_raw;
+        ctx.accounts.config.claim_amount = to_raw_amount(claim_amount_ui, decimals)?;
 
-        let mint_to_ix = MintTo{
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.mint_authority_ata.to_account_info(),
-            authority: ctx.accounts.mint_authority.to_account_info(),
+        let claim_cooldown = claim_cooldown.unwrap_or(DEFAULT_CLAIM_COOLDOWN);
+        require!(claim_cooldown >= 0, FaucetError::InvalidCooldown);
+        ctx.accounts.config.claim_cooldown = claim_cooldown;
+
+        ctx.accounts.config.lifetime_cap = match lifetime_cap {
+            Some(cap) => Some(to_raw_amount(cap, decimals)?),
+            None => None,
         };
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"mint_authority",
-            &[ctx.bumps.mint_authority],
-        ]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            mint_to_ix,
-            signer_seeds
-        );
-        mint_to(cpi_ctx, initial_supply)?;
-        Ok(())
+
+        mint_to_faucet(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority_ata,
+            initial_supply,
+        )
     }
 
+    /// Claims reopen `claim_cooldown` seconds after the previous one, until the
+    /// wallet has received `lifetime_cap`, within the global window budget.
+    /// The admin is not limited
     pub fn claim(ctx: Context<ClaimContext>) -> Result<()> {
-        if ctx.accounts.signer.key() != ctx.accounts.config.admin
-            && ctx.accounts.claim_record.claimed_at != 0
-        {
-            return err!(FaucetError::AlreadyClaimed);
+        require!(ctx.accounts.config.merkle_root.is_none(), FaucetError::AllowlistActive);
+
+        let clock = Clock::get()?;
+        let amount = ctx.accounts.config.claim_amount;
+        let claim_record = &mut ctx.accounts.claim_record;
+
+        if ctx.accounts.signer.key() != ctx.accounts.config.admin {
+            if claim_record.claimed_at != 0 {
+                let reopens_at = claim_record.claimed_at
+                    .checked_add(ctx.accounts.config.claim_cooldown)
+                    .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+                require!(clock.unix_timestamp >= reopens_at, FaucetError::AlreadyClaimed);
+            }
+            if let Some(lifetime_cap) = ctx.accounts.config.lifetime_cap {
+                let total_claimed = claim_record.total_claimed
+                    .checked_add(amount)
+                    .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+                require!(total_claimed <= lifetime_cap, FaucetError::LifetimeCapReached);
+            }
+            ctx.accounts.config.consume_window_budget(amount, clock.unix_timestamp)?;
         }
 
+        claim_record.user = ctx.accounts.signer.key();
+        claim_record.claimed_at = clock.unix_timestamp;
+        claim_record.total_claimed = claim_record.total_claimed
+            .checked_add(amount)
+            .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+
+        transfer_from_faucet(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority_ata,
+            &ctx.accounts.user_ata,
+            amount,
+        )
+    }
+
+    /// Allowlisted claim of `amount` raw tokens, `proof` links `(signer, amount)` to
+    /// `config.merkle_root`. Each wallet claims once per root, tracked apart from `claim`:
+    /// cooldown and lifetime cap do not apply, and allowlist payouts do not count towards the cap
+    pub fn claim_with_proof(ctx: Context<ClaimWithProofContext>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let root = ctx.accounts.config.merkle_root.ok_or(error!(FaucetError::AllowlistDisabled))?;
+        let leaf = allowlist_leaf(&ctx.accounts.signer.key(), amount);
+        require!(verify_proof(&proof, &root, leaf), FaucetError::InvalidProof);
+
+        let allowlist_claim = &mut ctx.accounts.allowlist_claim;
+        require!(allowlist_claim.claimed_at == 0, FaucetError::AlreadyClaimed);
+
+        // The admin is not rate limited, as in claim
         let clock = Clock::get()?;
-        ctx.accounts.claim_record.user = ctx.accounts.signer.key();
-        ctx.accounts.claim_record.claimed_at = clock.unix_timestamp;
+        if ctx.accounts.signer.key() != ctx.accounts.config.admin {
+            ctx.accounts.config.consume_window_budget(amount, clock.unix_timestamp)?;
+        }
+
+        allowlist_claim.user = ctx.accounts.signer.key();
+        allowlist_claim.root = root;
+        allowlist_claim.amount = amount;
+        allowlist_claim.claimed_at = clock.unix_timestamp;
+
+        transfer_from_faucet(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority_ata,
+            &ctx.accounts.user_ata,
+            amount,
+        )
+    }
+
+    /// Cap non-admin claims at `window_budget` whole tokens over any `window_duration` seconds,
+    /// None removes the limit
+    pub fn set_rate_limit(ctx: Context<AdminContext>, window_budget: Option<u64>, window_duration: i64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.window_budget = match window_budget {
+            Some(budget) => {
+                require!(window_duration > 0, FaucetError::InvalidWindow);
+                Some(to_raw_amount(budget, config.decimals)?)
+            }
+            None => None,
+        };
+        config.window_duration = window_duration;
+        // Start a fresh window under the new limit
+        config.window_start = Clock::get()?.unix_timestamp;
+        config.window_claimed = 0;
+        config.previous_window_claimed = 0;
+        Ok(())
+    }
+
+    /// Some(root) restricts claims to `claim_with_proof`, None reopens `claim` to everyone
+    pub fn set_merkle_root(ctx: Context<AdminContext>, merkle_root: Option<[u8; 32]>) -> Result<()> {
+        ctx.accounts.config.merkle_root = merkle_root;
+        Ok(())
+    }
+
+    /// `claim_amount` is in whole tokens, as in `initialize`
+    pub fn set_claim_amount(ctx: Context<AdminContext>, claim_amount: u64) -> Result<()> {
+        ctx.accounts.config.claim_amount = to_raw_amount(claim_amount, ctx.accounts.config.decimals)?;
+        Ok(())
+    }
+
+    /// Hand the faucet over, the mint's freeze authority moves along with it
+    pub fn set_admin(ctx: Context<SetAdminContext>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.admin = new_admin;
+
+        // The freeze authority starts with the deployer, who is the first admin
+        if ctx.accounts.mint.freeze_authority == Some(ctx.accounts.signer.key()).into() {
+            let set_authority_ix = SetAuthority{
+                current_authority: ctx.accounts.signer.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                set_authority_ix,
+            );
+            set_authority(cpi_ctx, AuthorityType::FreezeAccount, Some(new_admin))?;
+        }
+        Ok(())
+    }
+
+    /// Mint `amount` more raw tokens into the faucet, like `initial_supply`
+    /// Reopens a drained faucet
+    pub fn refill(ctx: Context<RefillContext>, amount: u64) -> Result<()> {
+        ctx.accounts.config.drained = false;
+
+        mint_to_faucet(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority_ata,
+            amount,
+        )
+    }
+
+    /// Close the faucet: the remaining supply goes to the admin's ATA and the faucet
+    /// ATA is closed, its rent returned to the admin
+    /// The config stays, marked drained, so the faucet_id and its mint can be reopened by refill
+    pub fn drain(ctx: Context<DrainContext>) -> Result<()> {
+        ctx.accounts.config.drained = true;
 
-        let amount = ctx.accounts.config.claim_amount;
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"mint_authority",
             &[ctx.bumps.mint_authority],
@@ -59,7 +232,7 @@ pub mod faucet {
 
         let transfer_ix = TransferChecked{
             from: ctx.accounts.mint_authority_ata.to_account_info(),
-            to: ctx.accounts.user_ata.to_account_info(),
+            to: ctx.accounts.admin_ata.to_account_info(),
             authority: ctx.accounts.mint_authority.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
         };
@@ -70,9 +243,21 @@ pub mod faucet {
         );
         transfer_checked(
             cpi_ctx,
-            amount,
-            9
+            ctx.accounts.mint_authority_ata.amount,
+            ctx.accounts.mint.decimals
         )?;
+
+        let close_ix = CloseAccount{
+            account: ctx.accounts.mint_authority_ata.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            close_ix,
+            signer_seeds
+        );
+        close_account(cpi_ctx)?;
         Ok(())
     }
 }
@@ -84,6 +269,14 @@ pub struct ClaimContext <'info>{
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = !config.drained @ FaucetError::FaucetDrained
+    )]
+    pub config: Account<'info, Config>,
+
     /// CHECK: This is the PDA that will be the mint authority
     #[account(
         seeds = [b"mint_authority"],
@@ -93,7 +286,7 @@ pub struct ClaimContext <'info>{
 
     #[account(
         mut,
-        seeds = [b"mint", mint_authority.key().as_ref()],
+        seeds = [b"mint", config.key().as_ref()],
         bump
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -119,17 +312,136 @@ pub struct ClaimContext <'info>{
         init_if_needed,
         payer = signer,
         space = 8 + ClaimRecord::INIT_SPACE,
-        seeds = [b"claim_record", signer.key().as_ref()],
+        seeds = [b"claim_record", config.key().as_ref(), signer.key().as_ref()],
         bump
     )]
     pub claim_record: Account<'info, ClaimRecord>,
 
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mint `amount` raw tokens into the faucet ATA, signed by the mint authority PDA
+fn mint_to_faucet<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    mint: &InterfaceAccount<'info, Mint>,
+    faucet_ata: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"mint_authority",
+        &[mint_authority_bump],
+    ]];
+
+    let mint_to_ix = MintTo{
+        mint: mint.to_account_info(),
+        to: faucet_ata.to_account_info(),
+        authority: mint_authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        mint_to_ix,
+        signer_seeds
+    );
+    mint_to(cpi_ctx, amount)
+}
+
+/// Pay `amount` raw tokens out of the faucet ATA, signed by the mint authority PDA
+fn transfer_from_faucet<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"mint_authority",
+        &[mint_authority_bump],
+    ]];
+
+    let transfer_ix = TransferChecked{
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+        authority: mint_authority.clone(),
+        mint: mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        transfer_ix,
+        signer_seeds
+    );
+    transfer_checked(
+        cpi_ctx,
+        amount,
+        mint.decimals
+    )
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithProofContext <'info>{
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
     #[account(
-        seeds = [b"config"],
-        bump
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = !config.drained @ FaucetError::FaucetDrained
     )]
     pub config: Account<'info, Config>,
 
+    /// CHECK: This is the PDA that will be the mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", config.key().as_ref()],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_authority_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // One per wallet and root, so an allowlist set again later can't be claimed twice
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + AllowlistClaim::INIT_SPACE,
+        seeds = [
+            b"allowlist_claim",
+            config.key().as_ref(),
+            config.merkle_root.unwrap_or_default().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub allowlist_claim: Account<'info, AllowlistClaim>,
+
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -139,7 +451,67 @@ pub struct ClaimContext <'info>{
 #[derive(InitSpace)]
 pub struct Config {
     pub admin: Pubkey,
+    // Namespace of this faucet, its mint and claim records are seeded with the config key
+    pub faucet_id: u64,
+    // Mirrors the mint, whole token amounts are scaled by 10^decimals
+    pub decimals: u8,
     pub claim_amount: u64,
+    // Seconds a wallet waits between claims
+    pub claim_cooldown: i64,
+    // Most a wallet can ever claim, None for no limit
+    pub lifetime_cap: Option<u64>,
+    // Allowlist of (wallet, amount) leaves, while set only claim_with_proof is open
+    pub merkle_root: Option<[u8; 32]>,
+    // Global rate limit: at most window_budget raw tokens per window_duration seconds,
+    // over a window sliding from the previous fixed window into the current one
+    pub window_budget: Option<u64>,
+    pub window_duration: i64,
+    pub window_start: i64,
+    pub window_claimed: u64,
+    pub previous_window_claimed: u64,
+    // Set by drain, claims stay closed until the next refill
+    pub drained: bool,
+}
+
+impl Config {
+    /// Count `amount` against the sliding window ending now
+    /// Usage of the last window_duration seconds is approximated as this fixed window's
+    /// claims plus the previous window's, weighted by how much of it still overlaps,
+    /// so the budget can't be spent twice around a window boundary
+    fn consume_window_budget(&mut self, amount: u64, now: i64) -> Result<()> {
+        let Some(window_budget) = self.window_budget else {
+            return Ok(());
+        };
+
+        let elapsed_windows = now
+            .checked_sub(self.window_start)
+            .ok_or(error!(FaucetError::ArithmeticOverflow))?
+            / self.window_duration;
+        if elapsed_windows > 0 {
+            // Only the window right before the current one still overlaps
+            self.previous_window_claimed = if elapsed_windows == 1 { self.window_claimed } else { 0 };
+            self.window_claimed = 0;
+            self.window_start = elapsed_windows
+                .checked_mul(self.window_duration)
+                .and_then(|offset| self.window_start.checked_add(offset))
+                .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+        }
+
+        let window_end = self.window_start
+            .checked_add(self.window_duration)
+            .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+        let overlap = window_end.saturating_sub(now).max(0) as u128;
+        let previous_weighted = self.previous_window_claimed as u128 * overlap / self.window_duration as u128;
+        let window_claimed = self.window_claimed
+            .checked_add(amount)
+            .ok_or(error!(FaucetError::ArithmeticOverflow))?;
+        require!(
+            previous_weighted + window_claimed as u128 <= window_budget as u128,
+            FaucetError::RateLimited
+        );
+        self.window_claimed = window_claimed;
+        Ok(())
+    }
 }
 
 #[account]
@@ -147,6 +519,16 @@ pub struct Config {
 pub struct ClaimRecord {
     pub user: Pubkey,
     pub claimed_at: i64,
+    pub total_claimed: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistClaim {
+    pub user: Pubkey,
+    pub root: [u8; 32],
+    pub amount: u64,
+    pub claimed_at: i64,
 }
 
 #[error_code]
@@ -154,16 +536,53 @@ pub enum FaucetError {
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
 
-    #[msg("User already claimed")]
+    #[msg("User already claimed, wait for the cooldown")]
     AlreadyClaimed,
+
+    #[msg("Wallet reached the lifetime claim cap")]
+    LifetimeCapReached,
+
+    #[msg("Claim cooldown cannot be negative")]
+    InvalidCooldown,
+
+    #[msg("Only the faucet admin can do this")]
+    Unauthorized,
+
+    #[msg("Claims are allowlisted, use claim_with_proof")]
+    AllowlistActive,
+
+    #[msg("No allowlist is set for this faucet")]
+    AllowlistDisabled,
+
+    #[msg("Invalid allowlist proof")]
+    InvalidProof,
+
+    #[msg("Faucet budget for this window is exhausted, try again later")]
+    RateLimited,
+
+    #[msg("Rate limit window must be positive")]
+    InvalidWindow,
+
+    #[msg("Faucet was drained, it reopens on refill")]
+    FaucetDrained,
 }
 
 #[derive(Accounts)]
+#[instruction(faucet_id: u64, decimals: u8)]
 pub struct InitializeContext <'info>{
 
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", faucet_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
     /// CHECK: This is the pda that will mint new token
     #[account(
         seeds = [b"mint_authority"],
@@ -174,11 +593,11 @@ pub struct InitializeContext <'info>{
     #[account(
         init,
         payer = signer,
-        mint::decimals = 9,
+        mint::decimals = decimals,
         mint::authority = mint_authority,
         mint::freeze_authority = signer, //deployer can freeze
         mint::token_program = token_program,
-        seeds = [b"mint", mint_authority.key().as_ref()],
+        seeds = [b"mint", config.key().as_ref()],
         bump
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -192,15 +611,135 @@ pub struct InitializeContext <'info>{
     )]
     pub mint_authority_ata: InterfaceAccount<'info, TokenAccount>,
 
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminContext <'info>{
+
+    pub signer: Signer<'info>,
+
     #[account(
-        init,
-        payer = signer,
-        space = 8 + Config::INIT_SPACE,
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = config.admin == signer.key() @ FaucetError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetAdminContext <'info>{
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = config.admin == signer.key() @ FaucetError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", config.key().as_ref()],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct RefillContext <'info>{
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = config.admin == signer.key() @ FaucetError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: This is the PDA that will be the mint authority
+    #[account(
+        seeds = [b"mint_authority"],
         bump
     )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint", config.key().as_ref()],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Closed by drain, recreated when a drained faucet is refilled
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = mint_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_authority_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DrainContext <'info>{
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.faucet_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = config.admin == signer.key() @ FaucetError::Unauthorized
+    )]
     pub config: Account<'info, Config>,
 
+    /// CHECK: This is the PDA that will be the mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"mint", config.key().as_ref()],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_authority_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub admin_ata: InterfaceAccount<'info, TokenAccount>,
+
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorProject } from "../../target/types/anchor_project";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "../utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -33,7 +33,7 @@ async function main() {
   console.log(`   Admin: ${wallet.publicKey.toString()}`);
 
   // Get current epoch
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
   const configAccount = await program.account.config.fetch(configPda);
   const currentEpoch = configAccount.currentEpoch.toNumber();
 
@@ -45,6 +45,7 @@ async function main() {
     const [poolPda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("pool"),
+        configPda.toBuffer(),
         Buffer.from([i]),
         configAccount.currentEpoch.toArrayLike(Buffer, "le", 8),
       ],
@@ -59,6 +60,7 @@ async function main() {
     const tx = await program.methods
       .initializePool(poolNbr)
       .accountsPartial({
+        config: configPda,
         signer: wallet.publicKey,
       })
       .remainingAccounts(
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorProject } from "../../target/types/anchor_project";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "../utils/common";
 
 // Default oracle queue from ephemeral-vrf-sdk
 const DEFAULT_ORACLE_QUEUE = new PublicKey("Cuj97ggrhhidhbu39TijNVqE74xvKJ69gDervRUXAxGh");
@@ -33,7 +33,7 @@ async function main() {
   console.log(`   Admin: ${wallet.publicKey.toString()}`);
 
   // Get current epoch
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
   const configAccount = await program.account.config.fetch(configPda);
   const currentEpoch = configAccount.currentEpoch.toNumber();
 
@@ -44,7 +44,7 @@ async function main() {
 
   // Derive epoch result PDA
   const [epochResultPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), configAccount.currentEpoch.toArrayLike(Buffer, "le", 8)],
+    [Buffer.from("epoch_result"), configPda.toBuffer(), configAccount.currentEpoch.toArrayLike(Buffer, "le", 8)],
     program.programId
   );
 
@@ -56,6 +56,7 @@ async function main() {
     const [poolPda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("pool"),
+        configPda.toBuffer(),
         Buffer.from([id]),
         configAccount.currentEpoch.toArrayLike(Buffer, "le", 8),
       ],
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorProject } from "../../target/types/anchor_project";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "../utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -28,7 +28,7 @@ async function main() {
   console.log(`   Program ID: ${program.programId.toString()}`);
   console.log(`   Admin: ${wallet.publicKey.toString()}`);
 
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
 
   // Get current config
   const configAccount = await program.account.config.fetch(configPda);
@@ -49,6 +49,7 @@ async function main() {
     const tx = await program.methods
       .updateResolutionType(resolutionType as any)
       .accountsPartial({
+        config: configPda,
         signer: wallet.publicKey,
       })
       .rpc();
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorProject } from "../target/types/anchor_project";
-import { getNetworkConfig } from "./utils/common";
+import { getNetworkConfig, getInstanceId, findConfigPda } from "./utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -10,7 +10,7 @@ async function main() {
   console.log(`\nChecking config on ${networkConfig.cluster}...\n`);
 
   const programId = new PublicKey("BQ2A6Mw7vLXB1Z7e6uLmHG5PXBizx6KLdgzXDKyMxu6t");
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+  const [configPda] = findConfigPda(programId, getInstanceId(args));
   console.log("Config PDA:", configPda.toString());
 
   const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorProject } from "../target/types/anchor_project";
-import { getNetworkConfig } from "./utils/common";
+import { getNetworkConfig, getInstanceId, findConfigPda } from "./utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -21,8 +21,9 @@ async function main() {
   anchor.setProvider(provider);
   const program = anchor.workspace.AnchorProject as anchor.Program<AnchorProject>;
 
+  const [configPda] = findConfigPda(programId, getInstanceId(args));
   const [epochResultPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), new anchor.BN(epoch).toArrayLike(Buffer, "le", 8)],
+    [Buffer.from("epoch_result"), configPda.toBuffer(), new anchor.BN(epoch).toArrayLike(Buffer, "le", 8)],
     programId
   );
   console.log("Epoch Result PDA:", epochResultPda.toString());
@@ -0,0 +1,90 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Faucet } from "../target/types/faucet";
+import { getNetworkConfig, loadWallet } from "./utils/common";
+
+const USAGE = `Usage:
+  ts-node scripts/faucet-admin.ts --network=<network> --set-claim-amount=<tokens>
+  ts-node scripts/faucet-admin.ts --network=<network> --refill=<raw amount>
+  ts-node scripts/faucet-admin.ts --network=<network> --set-admin=<pubkey>
+  ts-node scripts/faucet-admin.ts --network=<network> --set-merkle-root=<hex|none>
+  ts-node scripts/faucet-admin.ts --network=<network> --set-rate-limit=<tokens|none> [--window=<seconds>]
+  ts-node scripts/faucet-admin.ts --network=<network> --drain
+
+Every action takes an optional --faucet-id=<id> (default 0)`;
+
+function argValue(args: string[], name: string): string | undefined {
+  const arg = args.find((a) => a.startsWith(`--${name}=`));
+  return arg ? arg.split("=")[1] : undefined;
+}
+
+async function main() {
+  const args = process.argv.slice(2);
+  const networkConfig = getNetworkConfig(args);
+
+  const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
+
+  const walletKeypair = loadWallet();
+  const wallet = new anchor.Wallet(walletKeypair);
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: networkConfig.commitment,
+  });
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Faucet as Program<Faucet>;
+
+  console.log(`\nFaucet admin on ${networkConfig.cluster}`);
+  console.log(`Program ID: ${program.programId.toString()}`);
+  console.log(`Admin: ${wallet.publicKey.toString()}\n`);
+
+  const claimAmount = argValue(args, "set-claim-amount");
+  const refill = argValue(args, "refill");
+  const newAdmin = argValue(args, "set-admin");
+  const merkleRoot = argValue(args, "set-merkle-root");
+  const rateLimit = argValue(args, "set-rate-limit");
+  const faucetIdBuffer = Buffer.alloc(8);
+  faucetIdBuffer.writeBigUInt64LE(BigInt(argValue(args, "faucet-id") ?? 0));
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config"), faucetIdBuffer],
+    program.programId
+  );
+  const accounts = { signer: wallet.publicKey, config: configPda };
+
+  let tx: string;
+  if (claimAmount !== undefined) {
+    console.log(`Setting claim amount to ${claimAmount} tokens...`);
+    tx = await program.methods.setClaimAmount(new anchor.BN(claimAmount)).accounts(accounts).rpc();
+  } else if (refill !== undefined) {
+    console.log(`Refilling faucet with ${refill} raw tokens...`);
+    tx = await program.methods.refill(new anchor.BN(refill)).accounts(accounts).rpc();
+  } else if (newAdmin !== undefined) {
+    console.log(`Handing the faucet over to ${newAdmin}...`);
+    tx = await program.methods.setAdmin(new PublicKey(newAdmin)).accounts(accounts).rpc();
+  } else if (merkleRoot !== undefined) {
+    const root = merkleRoot === "none" ? null : [...Buffer.from(merkleRoot, "hex")];
+    console.log(root ? `Restricting claims to allowlist ${merkleRoot}...` : "Removing the allowlist...");
+    tx = await program.methods.setMerkleRoot(root).accounts(accounts).rpc();
+  } else if (rateLimit !== undefined) {
+    const window = parseInt(argValue(args, "window") ?? "3600");
+    const budget = rateLimit === "none" ? null : new anchor.BN(rateLimit);
+    console.log(budget ? `Limiting claims to ${rateLimit} tokens per ${window} seconds...` : "Removing the rate limit...");
+    tx = await program.methods.setRateLimit(budget, new anchor.BN(budget ? window : 0)).accounts(accounts).rpc();
+  } else if (args.includes("--drain")) {
+    console.log("Draining and closing the faucet (refill reopens it)...");
+    tx = await program.methods.drain().accounts(accounts).rpc();
+  } else {
+    console.log(USAGE);
+    return;
+  }
+
+  console.log(`Transaction: ${tx}`);
+  console.log(`✅ Done\n`);
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error("Error:", error);
+    process.exit(1);
+  });
@@ -3,12 +3,22 @@ import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { Faucet } from "../target/types/faucet";
 import { TOKEN_2022_PROGRAM_ID, getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import * as fs from "fs";
 import { getNetworkConfig, loadWallet } from "./utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
   const networkConfig = getNetworkConfig(args);
 
+  const faucetIdArg = args.find((arg) => arg.startsWith("--faucet-id="));
+  const faucetId = faucetIdArg ? parseInt(faucetIdArg.split("=")[1]) : 0;
+
+  // Allowlisted faucets: JSON file { "amount": "<raw amount>", "proof": ["<hex>", ...] }
+  const proofArg = args.find((arg) => arg.startsWith("--proof="));
+  const allowlistEntry = proofArg
+    ? JSON.parse(fs.readFileSync(proofArg.split("=")[1], "utf-8"))
+    : null;
+
   console.log(`\nClaiming tokens from Faucet on ${networkConfig.cluster}...\n`);
 
   const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
@@ -28,27 +38,26 @@ async function main() {
   const balance = await connection.getBalance(wallet.publicKey);
   console.log(`Balance: ${balance / anchor.web3.LAMPORTS_PER_SOL} SOL\n`);
 
-  const [mintAuthority] = PublicKey.findProgramAddressSync(
-    [Buffer.from("mint_authority")],
+  const faucetIdBuffer = Buffer.alloc(8);
+  faucetIdBuffer.writeBigUInt64LE(BigInt(faucetId));
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config"), faucetIdBuffer],
     program.programId
   );
 
   const [mint] = PublicKey.findProgramAddressSync(
-    [Buffer.from("mint"), mintAuthority.toBuffer()],
+    [Buffer.from("mint"), configPda.toBuffer()],
     program.programId
   );
 
+  console.log(`Faucet ID: ${faucetId}`);
   console.log(`Mint: ${mint.toString()}\n`);
 
-  const [configPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
-    program.programId
-  );
-
   const configAccount = await program.account.config.fetch(configPda);
   const claimAmount = configAccount.claimAmount.toNumber();
+  const unit = 10 ** configAccount.decimals;
 
-  console.log(`Claim amount: ${claimAmount / 1e9} tokens (${claimAmount} raw)\n`);
+  console.log(`Claim amount: ${claimAmount / unit} tokens (${claimAmount} raw)\n`);
 
   // Use Token-2022 program for ATA address calculation
   const userAta = getAssociatedTokenAddressSync(
@@ -70,7 +79,7 @@ async function main() {
       TOKEN_2022_PROGRAM_ID
     );
     balanceBefore = Number(tokenAccountBefore.amount);
-    console.log(`Balance before: ${balanceBefore / 1e9} tokens\n`);
+    console.log(`Balance before: ${balanceBefore / unit} tokens\n`);
   } catch (error: any) {
     console.log(`Balance before: 0 tokens (ATA doesn't exist yet)`);
     console.log(`Error: ${error.message}\n`);
@@ -79,12 +88,34 @@ async function main() {
   console.log("Claiming tokens...");
 
   try {
-    const tx = await program.methods
-      .claim()
-      .accounts({
-        signer: wallet.publicKey,
-      })
-      .rpc();
+    // Allowlist claims are recorded per wallet and root
+    const tx = allowlistEntry
+      ? await program.methods
+          .claimWithProof(
+            new anchor.BN(allowlistEntry.amount),
+            allowlistEntry.proof.map((node: string) => [...Buffer.from(node, "hex")])
+          )
+          .accountsPartial({
+            signer: wallet.publicKey,
+            config: configPda,
+            allowlistClaim: PublicKey.findProgramAddressSync(
+              [
+                Buffer.from("allowlist_claim"),
+                configPda.toBuffer(),
+                Buffer.from(configAccount.merkleRoot ?? Buffer.alloc(32)),
+                wallet.publicKey.toBuffer(),
+              ],
+              program.programId
+            )[0],
+          })
+          .rpc()
+      : await program.methods
+          .claim()
+          .accounts({
+            signer: wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
 
     console.log(`Transaction: ${tx}`);
     console.log(`✅ Claim successful!\n`);
@@ -102,8 +133,8 @@ async function main() {
 
       const balanceAfter = Number(tokenAccount.amount);
       console.log(`Token Account: ${userAta.toString()}`);
-      console.log(`Balance after: ${balanceAfter / 1e9} tokens`);
-      console.log(`Claimed: ${(balanceAfter - balanceBefore) / 1e9} tokens\n`);
+      console.log(`Balance after: ${balanceAfter / unit} tokens`);
+      console.log(`Claimed: ${(balanceAfter - balanceBefore) / unit} tokens\n`);
     } catch (error: any) {
       console.log(`⚠️  Could not fetch balance after claim`);
       console.log(`Token account: ${userAta.toString()}`);
@@ -10,19 +10,39 @@ async function main() {
   const args = process.argv.slice(2);
   const networkConfig = getNetworkConfig(args);
 
+  const faucetIdArg = args.find((arg) => arg.startsWith("--faucet-id="));
+  const faucetId = faucetIdArg ? parseInt(faucetIdArg.split("=")[1]) : 0;
+
+  const decimalsArg = args.find((arg) => arg.startsWith("--decimals="));
+  const decimals = decimalsArg ? parseInt(decimalsArg.split("=")[1]) : 9;
+
   const amountArg = args.find((arg) => arg.startsWith("--amount="));
   const initialSupply = amountArg
     ? BigInt(amountArg.split("=")[1])
-    : BigInt(10_000_000_000) * BigInt(10 ** 9);
+    : BigInt(10_000_000_000) * BigInt(10 ** decimals);
 
   const claimAmountArg = args.find((arg) => arg.startsWith("--claim-amount="));
   const claimAmount = claimAmountArg
     ? parseInt(claimAmountArg.split("=")[1])
     : null;
 
+  const cooldownArg = args.find((arg) => arg.startsWith("--cooldown="));
+  const claimCooldown = cooldownArg
+    ? parseInt(cooldownArg.split("=")[1])
+    : null;
+
+  const lifetimeCapArg = args.find((arg) => arg.startsWith("--lifetime-cap="));
+  const lifetimeCap = lifetimeCapArg
+    ? parseInt(lifetimeCapArg.split("=")[1])
+    : null;
+
   console.log(`\nInitializing Faucet on ${networkConfig.cluster}...\n`);
-  console.log(`Initial Supply: ${initialSupply.toString()} (${Number(initialSupply) / 1e9} tokens)`);
-  console.log(`Claim Amount: ${claimAmount ?? 10_000} tokens\n`);
+  console.log(`Faucet ID: ${faucetId}`);
+  console.log(`Decimals: ${decimals}`);
+  console.log(`Initial Supply: ${initialSupply.toString()} (${Number(initialSupply) / 10 ** decimals} tokens)`);
+  console.log(`Claim Amount: ${claimAmount ?? 10_000} tokens`);
+  console.log(`Claim Cooldown: ${claimCooldown ?? 86_400} seconds`);
+  console.log(`Lifetime Cap: ${lifetimeCap !== null ? `${lifetimeCap} tokens` : "none"}\n`);
 
   const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
 
@@ -46,12 +66,20 @@ async function main() {
     program.programId
   );
 
+  const faucetIdBuffer = Buffer.alloc(8);
+  faucetIdBuffer.writeBigUInt64LE(BigInt(faucetId));
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config"), faucetIdBuffer],
+    program.programId
+  );
+
   const [mint] = PublicKey.findProgramAddressSync(
-    [Buffer.from("mint"), mintAuthority.toBuffer()],
+    [Buffer.from("mint"), configPda.toBuffer()],
     program.programId
   );
 
   console.log(`PDAs:`);
+  console.log(`Config: ${configPda.toString()}`);
   console.log(`Mint Authority: ${mintAuthority.toString()}`);
   console.log(`Mint: ${mint.toString()}\n`);
 
@@ -65,7 +93,10 @@ async function main() {
       const deploymentInfo = {
         network: networkConfig.cluster,
         programId: program.programId.toString(),
+        faucetId,
+        config: configPda.toString(),
         mint: mint.toString(),
+        decimals,
         mintAuthority: mintAuthority.toString(),
         deployer: wallet.publicKey.toString(),
         timestamp: new Date().toISOString(),
@@ -93,8 +124,12 @@ async function main() {
   try {
     const tx = await program.methods
       .initialize(
+        new anchor.BN(faucetId),
+        decimals,
         new anchor.BN(initialSupply.toString()),
-        claimAmount !== null ? new anchor.BN(claimAmount) : null
+        claimAmount !== null ? new anchor.BN(claimAmount) : null,
+        claimCooldown !== null ? new anchor.BN(claimCooldown) : null,
+        lifetimeCap !== null ? new anchor.BN(lifetimeCap) : null
       )
       .accounts({
         signer: wallet.publicKey,
@@ -107,7 +142,10 @@ async function main() {
     const deploymentInfo = {
       network: networkConfig.cluster,
       programId: program.programId.toString(),
+      faucetId,
+      config: configPda.toString(),
       mint: mint.toString(),
+      decimals,
       mintAuthority: mintAuthority.toString(),
       deployer: wallet.publicKey.toString(),
       timestamp: new Date().toISOString(),
@@ -5,7 +5,7 @@ import { AnchorProject } from "../target/types/anchor_project";
 import { TOKEN_2022_PROGRAM_ID, getMint } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
-import { getNetworkConfig, loadWallet } from "./utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "./utils/common";
 
 // Epoch duration constant (1 minute = 60 seconds)
 const EPOCH_DURATION = 60;
@@ -15,12 +15,16 @@ const EPOCH_DURATION = 60;
 const WEIGHT_RATE_NUMERATOR = 10;
 const WEIGHT_RATE_DENOMINATOR = 300;
 
+// Default position price in the mint's base units, override with --position-price
+const DEFAULT_POSITION_PRICE = "1000000000000";
+
 async function main() {
   const args = process.argv.slice(2);
   const networkConfig = getNetworkConfig(args);
 
   const mintArg = args.find((arg) => arg.startsWith("--mint="));
   const resolverArg = args.find((arg) => arg.startsWith("--resolver="));
+  const priceArg = args.find((arg) => arg.startsWith("--position-price="));
 
   if (!mintArg) {
     throw new Error(
@@ -39,6 +43,7 @@ async function main() {
 
   const mintAddress = mintArg.split("=")[1];
   const resolverAddress = resolverArg.split("=")[1];
+  const positionPrice = new BN(priceArg ? priceArg.split("=")[1] : DEFAULT_POSITION_PRICE);
 
   console.log(`\n1 - Initializing program on ${networkConfig.cluster}...`);
   console.log(`   RPC: ${networkConfig.rpcUrl}`);
@@ -99,13 +104,12 @@ async function main() {
     );
   }
 
-  console.log(`3 - Resolver address: ${resolver.toString()}\n`);
+  console.log(`3 - Resolver address: ${resolver.toString()}`);
+  console.log(`   Position price: ${positionPrice.toString()}\n`);
 
   // Check if config already exists
-  const [configPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
-    program.programId
-  );
+  const instanceId = getInstanceId(args);
+  const [configPda] = findConfigPda(program.programId, instanceId);
 
   try {
     const existingConfig = await program.account.config.fetch(configPda);
@@ -128,8 +132,10 @@ async function main() {
         resolutionType,
         resolver,
         new BN(EPOCH_DURATION),
+        positionPrice,
         new BN(WEIGHT_RATE_NUMERATOR),
-        new BN(WEIGHT_RATE_DENOMINATOR)
+        new BN(WEIGHT_RATE_DENOMINATOR),
+        new BN(instanceId)
       )
       .accountsPartial({
         signer: wallet.publicKey,
@@ -145,6 +151,8 @@ async function main() {
   const deploymentInfo = {
     network: networkConfig.cluster,
     programId: program.programId.toString(),
+    instanceId,
+    config: configPda.toString(),
     mint: mint.toString(),
     resolver: resolver.toString(),
     deployer: wallet.publicKey.toString(),
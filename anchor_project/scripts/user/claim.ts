@@ -5,7 +5,7 @@ import { AnchorProject } from "../../target/types/anchor_project";
 import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "../utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -44,10 +44,13 @@ async function main() {
 
   console.log(`   Mint: ${mint.toString()}\n`);
 
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
+
   // Check commitment exists
   const [commitmentPda] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("commitment"),
+      configPda.toBuffer(),
       wallet.publicKey.toBuffer(),
       Buffer.from([poolId]),
       new BN(epoch).toArrayLike(Buffer, "le", 8),
@@ -68,7 +71,7 @@ async function main() {
 
   // Check epoch result
   const [epochResultPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), new BN(epoch).toArrayLike(Buffer, "le", 8)],
+    [Buffer.from("epoch_result"), configPda.toBuffer(), new BN(epoch).toArrayLike(Buffer, "le", 8)],
     program.programId
   );
 
@@ -117,6 +120,7 @@ async function main() {
     const tx = await program.methods
       .claim(poolId, new BN(epoch))
       .accountsPartial({
+        config: configPda,
         signer: wallet.publicKey,
         tokenMint: mint,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
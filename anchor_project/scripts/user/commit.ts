@@ -1,8 +1,8 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
-import { PublicKey } from "@solana/web3.js";
+import { PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
 import { AnchorProject } from "../../target/types/anchor_project";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda, positionAccountMetas } from "../utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -10,14 +10,16 @@ async function main() {
   const positionIdArg = args.find((arg) => arg.startsWith("--position-id="));
   const poolIdArg = args.find((arg) => arg.startsWith("--pool-id="));
 
-  const positionId = positionIdArg ? parseInt(positionIdArg.split("=")[1]) : undefined;
+  // A comma separated list (--position-id=0,1,2) commits every position in one instruction
+  const positionIds = positionIdArg ? positionIdArg.split("=")[1].split(",").map((id) => parseInt(id)) : undefined;
   const poolId = poolIdArg ? parseInt(poolIdArg.split("=")[1]) : undefined;
 
-  if (positionId === undefined || poolId === undefined) {
+  if (positionIds === undefined || positionIds.some(isNaN) || poolId === undefined) {
     throw new Error("Missing required arguments: --position-id and --pool-id");
   }
+  const positionId = positionIds[0];
 
-  console.log(`\n🎯 Committing Position ${positionId} to Pool ${poolId} on ${networkConfig.cluster}...\n`);
+  console.log(`\n🎯 Committing Position ${positionIds.join(", ")} to Pool ${poolId} on ${networkConfig.cluster}...\n`);
 
   const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
   const walletKeypair = loadWallet();
@@ -31,21 +33,25 @@ async function main() {
   console.log(`   User: ${wallet.publicKey.toString()}`);
 
   // Get config and current epoch
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
   const configAccount = await program.account.config.fetch(configPda);
   const currentEpoch = configAccount.currentEpoch.toNumber();
 
   console.log(`   Current Epoch: ${currentEpoch}\n`);
 
-  // Derive position PDA
-  const [positionPda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("position"),
-      wallet.publicKey.toBuffer(),
-      new BN(positionId).toArrayLike(Buffer, "le", 8),
-    ],
-    program.programId
+  // Derive position PDAs
+  const positionPdas = positionIds.map((id) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("position"),
+        configPda.toBuffer(),
+        wallet.publicKey.toBuffer(),
+        new BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0]
   );
+  const positionPda = positionPdas[0];
 
   // Check position exists
   const positionAccount = await program.account.positionAccount.fetch(positionPda);
@@ -58,6 +64,7 @@ async function main() {
   const [poolPda] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("pool"),
+      configPda.toBuffer(),
       Buffer.from([poolId]),
       configAccount.currentEpoch.toArrayLike(Buffer, "le", 8),
     ],
@@ -80,6 +87,7 @@ async function main() {
   const [commitmentPda] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("commitment"),
+      configPda.toBuffer(),
       wallet.publicKey.toBuffer(),
       Buffer.from([poolId]),
       configAccount.currentEpoch.toArrayLike(Buffer, "le", 8),
@@ -90,16 +98,29 @@ async function main() {
   console.log(`\n   Commitment PDA: ${commitmentPda.toString()}`);
 
   try {
-    const tx = await program.methods
-      .commit(new BN(positionId), poolId)
-      .accountsPartial({
-        signer: wallet.publicKey,
-        position: positionPda,
-        commitment: commitmentPda,
-      })
-      .rpc();
-
-    console.log(`\n   ✅ Position committed successfully!`);
+    const tx = positionIds.length === 1
+      ? await program.methods
+          .commit(new BN(positionId), poolId)
+          .accountsPartial({
+            config: configPda,
+            signer: wallet.publicKey,
+            position: positionPda,
+            commitment: commitmentPda,
+          })
+          .rpc()
+      : await program.methods
+          .commitMany(poolId)
+          // Burning every position's token needs more than the default compute limit
+          .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+          .accountsPartial({
+            config: configPda,
+            signer: wallet.publicKey,
+            commitment: commitmentPda,
+          })
+          .remainingAccounts(positionPdas.flatMap((pda) => positionAccountMetas(program.programId, wallet.publicKey, pda)))
+          .rpc();
+
+    console.log(`\n   ✅ ${positionIds.length === 1 ? "Position" : "Positions"} committed successfully!`);
     console.log(`   Transaction: ${tx}`);
 
     // Get transaction fee
@@ -118,7 +139,7 @@ async function main() {
     console.log(`   - Weight: ${commitmentAccount.weight.toNumber()}`);
     console.log(`   - Position Amount: ${commitmentAccount.positionAmount.toNumber()}`);
 
-    console.log(`\n   ℹ️  Position has been burned. Weight is calculated based on time held.`);
+    console.log(`\n   ℹ️  Position and its token have been burned. Weight is calculated based on time held.`);
     console.log(`   ℹ️  Wait for admin to resolve the epoch, then claim your rewards!\n`);
   } catch (error: any) {
     throw error;
@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { AnchorProject } from "../../target/types/anchor_project";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda } from "../utils/common";
+
+const DEFAULT_ORACLE_QUEUE = new PublicKey("Cuj97ggrhhidhbu39TijNVqE74xvKJ69gDervRUXAxGh");
+
+async function main() {
+  const args = process.argv.slice(2);
+  const networkConfig = getNetworkConfig(args);
+
+  const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
+  const walletKeypair = loadWallet();
+  const wallet = new anchor.Wallet(walletKeypair);
+  const provider = new anchor.AnchorProvider(connection, wallet, { commitment: networkConfig.commitment });
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AnchorProject as Program<AnchorProject>;
+
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
+  const configAccount = await program.account.config.fetch(configPda);
+  const poolNbr = configAccount.poolsPerEpoch;
+
+  if (poolNbr === 0) {
+    throw new Error("Automatic rollover is disabled (pools_per_epoch is 0)");
+  }
+
+  // --rollover resolves the current epoch and opens the next one in the same instruction
+  const rollover = args.includes("--rollover");
+  const currentEpoch = configAccount.currentEpoch.toNumber();
+  const openedEpoch = rollover ? currentEpoch + 1 : currentEpoch;
+
+  console.log(`\n⚙️  Opening epoch ${openedEpoch} with ${poolNbr} pool(s) on ${networkConfig.cluster}...\n`);
+  console.log(`   Program ID: ${program.programId.toString()}`);
+  console.log(`   Cranker: ${wallet.publicKey.toString()}`);
+
+  const poolPdas = (epoch: number, count: number) =>
+    Array.from({ length: count }, (_, i) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("pool"),
+          configPda.toBuffer(),
+          Buffer.from([i]),
+          new anchor.BN(epoch).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0]
+    );
+
+  let poolPdaList = poolPdas(openedEpoch, poolNbr);
+  if (rollover) {
+    const [epochResultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("epoch_result"), configPda.toBuffer(), new anchor.BN(currentEpoch).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const epochResult = await program.account.epochResult.fetch(epochResultPda);
+    console.log(`   Resolving epoch ${currentEpoch} first`);
+    poolPdaList = [...poolPdas(currentEpoch, epochResult.poolCount), ...poolPdaList];
+  }
+
+  try {
+    const method = rollover
+      ? program.methods.rollover(0).accountsPartial({
+          config: configPda,
+          signer: wallet.publicKey,
+          oracleQueue: DEFAULT_ORACLE_QUEUE,
+          nextEpochResult: PublicKey.findProgramAddressSync(
+            [Buffer.from("epoch_result"), configPda.toBuffer(), new anchor.BN(openedEpoch).toArrayLike(Buffer, "le", 8)],
+            program.programId
+          )[0],
+        })
+      : program.methods.crank().accountsPartial({
+          config: configPda,
+          signer: wallet.publicKey,
+        });
+    const tx = await method
+      .remainingAccounts(
+        poolPdaList.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .rpc();
+
+    console.log(`\n   ✅  Epoch opened successfully!`);
+    console.log(`   Transaction: ${tx}`);
+  } catch (error: any) {
+    console.log("❌ Error:", error.message);
+  }
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error("\n❌ Error:", error.message);
+    process.exit(1);
+  });
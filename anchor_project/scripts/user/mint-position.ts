@@ -1,17 +1,22 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { PublicKey } from "@solana/web3.js";
+import { PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
 import { AnchorProject } from "../../target/types/anchor_project";
 import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
-import { getNetworkConfig, loadWallet } from "../utils/common";
+import { getNetworkConfig, loadWallet, getInstanceId, findConfigPda, positionAccountMetas } from "../utils/common";
 
 async function main() {
   const args = process.argv.slice(2);
   const networkConfig = getNetworkConfig(args);
+  const countArg = args.find((arg) => arg.startsWith("--count="));
+  const count = countArg ? parseInt(countArg.split("=")[1]) : 1;
+  if (isNaN(count) || count < 1 || count > 5) {
+    throw new Error("--count must be between 1 and 5");
+  }
 
-  console.log(`\n🎫 Minting Position on ${networkConfig.cluster}...\n`);
+  console.log(`\n🎫 Minting ${count > 1 ? `${count} Positions` : "Position"} on ${networkConfig.cluster}...\n`);
 
   const connection = new anchor.web3.Connection(networkConfig.rpcUrl, networkConfig.commitment);
   const walletKeypair = loadWallet();
@@ -34,7 +39,7 @@ async function main() {
   const mint = new PublicKey(deployment.mint);
 
   // Get config
-  const [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+  const [configPda] = findConfigPda(program.programId, getInstanceId(args));
   const configAccount = await program.account.config.fetch(configPda);
   const positionPrice = configAccount.positionPrice.toNumber();
 
@@ -58,15 +63,15 @@ async function main() {
   const balance = Buffer.from(accountInfo.data).readBigUInt64LE(64);
   console.log(`   Current Balance: ${Number(balance) / 10 ** 9} tokens`);
 
-  if (Number(balance) < positionPrice) {
+  if (Number(balance) < positionPrice * count) {
     throw new Error(
-      `Insufficient balance. Need ${positionPrice / 10 ** 9} tokens, have ${Number(balance) / 10 ** 9} tokens`
+      `Insufficient balance. Need ${(positionPrice * count) / 10 ** 9} tokens, have ${Number(balance) / 10 ** 9} tokens`
     );
   }
 
   // Get user's position count from user_state account
   const [userStatePda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_state"), wallet.publicKey.toBuffer()],
+    [Buffer.from("user_state"), configPda.toBuffer(), wallet.publicKey.toBuffer()],
     program.programId
   );
 
@@ -85,6 +90,7 @@ async function main() {
   const [positionPda] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("position"),
+      configPda.toBuffer(),
       wallet.publicKey.toBuffer(),
       new anchor.BN(positionId).toArrayLike(Buffer, "le", 8),
     ],
@@ -93,16 +99,43 @@ async function main() {
 
   console.log(`   Position PDA: ${positionPda.toString()}\n`);
 
+  // Batch mints pass every new position's accounts, in user index order
+  const batchPdas = [...Array(count).keys()].map((i) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("position"),
+        configPda.toBuffer(),
+        wallet.publicKey.toBuffer(),
+        new anchor.BN(positionId + i).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0]
+  );
+
   try {
-    const tx = await program.methods
-      .mintPosition()
-      .accountsPartial({
-        signer: wallet.publicKey,
-        position: positionPda,
-        mint: mint,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-      })
-      .rpc();
+    const tx = count === 1
+      ? await program.methods
+          .mintPosition()
+          .accountsPartial({
+            config: configPda,
+            signer: wallet.publicKey,
+            position: positionPda,
+            mint: mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc()
+      : await program.methods
+          .mintPositions(count)
+          // Minting every position's token needs more than the default compute limit
+          .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+          .accountsPartial({
+            config: configPda,
+            signer: wallet.publicKey,
+            mint: mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(batchPdas.flatMap((pda) => positionAccountMetas(program.programId, wallet.publicKey, pda)))
+          .rpc();
 
     console.log(`\n   ✅ Position minted successfully!`);
     console.log(`   Transaction: ${tx}`);
@@ -126,7 +159,11 @@ async function main() {
     const newAccountInfo = await connection.getAccountInfo(userAta);
     const newBalance = Buffer.from(newAccountInfo!.data).readBigUInt64LE(64);
     console.log(`\n   New Balance: ${Number(newBalance) / 10 ** 9} tokens`);
-    console.log(`   Spent: ${positionPrice / 10 ** 9} tokens\n`);
+    console.log(`   Spent: ${Number(balance - newBalance) / 10 ** 9} tokens`);
+    if (count > 1) {
+      console.log(`   Position IDs: ${positionId} to ${positionId + count - 1}`);
+    }
+    console.log();
   } catch (error: any) {
     throw error;
   }
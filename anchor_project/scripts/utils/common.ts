@@ -1,4 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
 
@@ -68,3 +69,59 @@ export function getNetworkConfig(args: string[]): NetworkConfig {
 
   return config;
 }
+
+/**
+ * Get the protocol instance id from command line args or env var
+ * @param args Command line arguments
+ * @returns Instance id, defaults to 0
+ */
+export function getInstanceId(args: string[]): number {
+  const instanceArg = args.find((arg) => arg.startsWith("--instance="));
+  const instance = instanceArg
+    ? instanceArg.split("=")[1]
+    : process.env.PROTOCOL_INSTANCE_ID || "0";
+  return parseInt(instance);
+}
+
+/**
+ * Derive the config PDA of a protocol instance, every other PDA is seeded with it
+ * @param programId Protocol program id
+ * @param instanceId Instance id passed to initialize
+ * @returns Config PDA and bump
+ */
+export function findConfigPda(
+  programId: anchor.web3.PublicKey,
+  instanceId: number
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config"), new anchor.BN(instanceId).toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * (position, position mint, position ATA) metas for a position PDA, as expected in the
+ * remaining accounts of mint_positions and commit_many
+ */
+export function positionAccountMetas(
+  programId: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey,
+  positionPda: anchor.web3.PublicKey
+): anchor.web3.AccountMeta[] {
+  const [positionMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("position_mint"), positionPda.toBuffer()],
+    programId
+  );
+  const positionAta = getAssociatedTokenAddressSync(
+    positionMint,
+    owner,
+    false,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  return [positionPda, positionMint, positionAta].map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  }));
+}
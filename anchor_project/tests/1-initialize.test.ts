@@ -5,7 +5,7 @@ import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
 import { LiteSVM } from "litesvm";
 import { expect } from "chai";
 import { AnchorProject } from "../target/types/anchor_project";
-import { findConfigPda, findPoolPda, findEpochResultPda, EPOCH_DURATION, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR, logSvmResult } from "./utils";
+import { findConfigPda, findPoolPda, findEpochResultPda, EPOCH_DURATION, POSITION_PRICE, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR, INSTANCE_ID, logSvmResult } from "./utils";
 import { createAndMintToken2022 } from "./utils";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
 
@@ -46,7 +46,7 @@ describe("Initialize", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const tx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -83,7 +83,7 @@ describe("Initialize", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const tx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -126,6 +126,7 @@ describe("Initialize", () => {
     const initPoolTx = await program.methods
       .initializePool(numPools)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts(
@@ -162,7 +163,7 @@ describe("Initialize", () => {
 
     // Verify EpochResult was created correctly
     const [epochResultPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("epoch_result"), Buffer.from(new BN(epoch).toArray("le", 8))],
+      [Buffer.from("epoch_result"), configPda.toBuffer(), Buffer.from(new BN(epoch).toArray("le", 8))],
       program.programId
     );
 
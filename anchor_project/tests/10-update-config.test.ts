@@ -0,0 +1,121 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPoolPda,
+  findEpochResultPda,
+  EPOCH_DURATION,
+  send,
+  setupProgram,
+  fetchConfig,
+} from "./utils";
+
+describe("Update Config", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let payer: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  beforeEach(async () => {
+    ({ svm, program, admin: payer, mint, configPda } = await setupProgram());
+  });
+
+  it("Stages updates and applies them when the next epoch opens", async () => {
+    const newPrice = new BN(500).mul(new BN(LAMPORTS_PER_SOL));
+    const newResolver = Keypair.generate().publicKey;
+
+    send(svm, await program.methods.updatePositionPrice(newPrice).accounts({ config: configPda, signer: payer.publicKey }).transaction(), payer);
+    send(svm, await program.methods.updateEpochDuration(new BN(120)).accounts({ config: configPda, signer: payer.publicKey }).transaction(), payer);
+    send(svm, await program.methods.updateWeightModel({ constant: {} }, new BN(1), new BN(10)).accounts({ config: configPda, signer: payer.publicKey }).transaction(), payer);
+    send(svm, await program.methods.updateResolver(newResolver).accounts({ config: configPda, signer: payer.publicKey }).transaction(), payer);
+
+    // Nothing applied yet, only staged
+    let config = fetchConfig(svm, program);
+    expect(config.positionPrice.toString()).to.equal(new BN(1000).mul(new BN(LAMPORTS_PER_SOL)).toString());
+    expect(config.epochDuration.toNumber()).to.equal(EPOCH_DURATION);
+    expect(config.weightModel).to.deep.equal({ timeBased: {} });
+    expect(config.resolver.toString()).to.equal(payer.publicKey.toString());
+    expect(config.pendingUpdate.positionPrice.toString()).to.equal(newPrice.toString());
+    expect(config.pendingUpdate.resolver.toString()).to.equal(newResolver.toString());
+
+    // Opening epoch 1 applies the pending update
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    const initPoolTx = await program.methods
+      .initializePool(1)
+      .accounts({ config: configPda, signer: payer.publicKey })
+      .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+      .transaction();
+    send(svm, initPoolTx, payer);
+
+    config = fetchConfig(svm, program);
+    expect(config.positionPrice.toString()).to.equal(newPrice.toString());
+    expect(config.epochDuration.toNumber()).to.equal(120);
+    expect(config.weightModel).to.deep.equal({ constant: {} });
+    expect(config.weightRateNumerator.toNumber()).to.equal(1);
+    expect(config.weightRateDenominator.toNumber()).to.equal(10);
+    expect(config.resolver.toString()).to.equal(newResolver.toString());
+    expect(config.pendingUpdate.positionPrice).to.be.null;
+    expect(config.pendingUpdate.resolver).to.be.null;
+
+    const [epochResultPda] = findEpochResultPda(1, program.programId);
+    const epochResult = program.coder.accounts.decode(
+      "epochResult",
+      Buffer.from(svm.getAccount(epochResultPda)!.data)
+    );
+    expect(epochResult.positionPrice.toString()).to.equal(newPrice.toString());
+  });
+
+  it("Rejects updates from a non-admin signer", async () => {
+    const attacker = Keypair.generate();
+    svm.airdrop(attacker.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+
+    const tx = await program.methods
+      .updatePositionPrice(new BN(1))
+      .accountsPartial({ signer: attacker.publicKey, config: configPda })
+      .transaction();
+    const result = send(svm, tx, attacker);
+
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+
+    const resolverResult = send(
+      svm,
+      await program.methods
+        .updateResolver(attacker.publicKey)
+        .accountsPartial({ signer: attacker.publicKey, config: configPda })
+        .transaction(),
+      attacker
+    );
+    expect(typeof resolverResult.err).to.equal("function");
+    expect(resolverResult.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+  });
+
+  it("Rejects invalid values", async () => {
+    const priceResult = send(
+      svm,
+      await program.methods.updatePositionPrice(new BN(0)).accounts({ config: configPda, signer: payer.publicKey }).transaction(),
+      payer
+    );
+    expect(typeof priceResult.err).to.equal("function");
+    expect(priceResult.meta().logs().join("\n")).to.include("InvalidPositionPrice");
+
+    const durationResult = send(
+      svm,
+      await program.methods.updateEpochDuration(new BN(0)).accounts({ config: configPda, signer: payer.publicKey }).transaction(),
+      payer
+    );
+    expect(typeof durationResult.err).to.equal("function");
+    expect(durationResult.meta().logs().join("\n")).to.include("InvalidEpochDuration");
+
+    const rateResult = send(
+      svm,
+      await program.methods.updateWeightModel({ timeBased: {} }, new BN(1), new BN(0)).accounts({ config: configPda, signer: payer.publicKey }).transaction(),
+      payer
+    );
+    expect(typeof rateResult.err).to.equal("function");
+    expect(rateResult.meta().logs().join("\n")).to.include("InvalidWeightRate");
+  });
+});
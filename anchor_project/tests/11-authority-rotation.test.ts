@@ -0,0 +1,123 @@
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPoolPda,
+  send,
+  setupProgram,
+  fetchConfig,
+} from "./utils";
+
+describe("Admin and Resolver Rotation", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let newAdmin: Keypair;
+  let newResolver: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  beforeEach(async () => {
+    newAdmin = Keypair.generate();
+    newResolver = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    svm.airdrop(newAdmin.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+    svm.airdrop(newResolver.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+  });
+
+  it("Hands over the admin role after the new key accepts", async () => {
+    send(svm, await program.methods.proposeAdmin(newAdmin.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    let config = fetchConfig(svm, program);
+    expect(config.admin.toString()).to.equal(admin.publicKey.toString());
+    expect(config.pendingAdmin.toString()).to.equal(newAdmin.publicKey.toString());
+
+    const result = send(svm, await program.methods.acceptAdmin().accounts({ config: configPda, signer: newAdmin.publicKey }).transaction(), newAdmin);
+    expect(result.err).to.be.undefined;
+
+    config = fetchConfig(svm, program);
+    expect(config.admin.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(config.pendingAdmin).to.be.null;
+
+    // The old admin lost its rights
+    const oldAdminResult = send(
+      svm,
+      await program.methods.proposeAdmin(admin.publicKey).accountsPartial({ signer: admin.publicKey, config: configPda }).transaction(),
+      admin
+    );
+    expect(typeof oldAdminResult.err).to.equal("function");
+    expect(oldAdminResult.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+  });
+
+  it("Hands over the resolver role after the new key accepts", async () => {
+    send(svm, await program.methods.proposeResolver(newResolver.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.acceptResolver().accounts({ config: configPda, signer: newResolver.publicKey }).transaction(), newResolver);
+
+    const config = fetchConfig(svm, program);
+    expect(config.resolver.toString()).to.equal(newResolver.publicKey.toString());
+    expect(config.pendingResolver).to.be.null;
+  });
+
+  it("Rejects acceptance from a key that was not proposed", async () => {
+    send(svm, await program.methods.proposeAdmin(newAdmin.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    const result = send(svm, await program.methods.acceptAdmin().accounts({ config: configPda, signer: newResolver.publicKey }).transaction(), newResolver);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("UnauthorizedPendingAdmin");
+  });
+
+  it("Cancels a pending proposal", async () => {
+    send(svm, await program.methods.proposeResolver(newResolver.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.cancelResolverProposal().accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    expect(fetchConfig(svm, program).pendingResolver).to.be.null;
+
+    const acceptResult = send(svm, await program.methods.acceptResolver().accounts({ config: configPda, signer: newResolver.publicKey }).transaction(), newResolver);
+    expect(typeof acceptResult.err).to.equal("function");
+    expect(acceptResult.meta().logs().join("\n")).to.include("NoPendingResolver");
+
+    const cancelResult = send(svm, await program.methods.cancelAdminProposal().accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    expect(typeof cancelResult.err).to.equal("function");
+    expect(cancelResult.meta().logs().join("\n")).to.include("NoPendingAdmin");
+  });
+
+  it("Keeps an accepted resolver when an admin update was staged before", async () => {
+    const staged = Keypair.generate().publicKey;
+    send(svm, await program.methods.updateResolver(staged).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.proposeResolver(newResolver.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.acceptResolver().accounts({ config: configPda, signer: newResolver.publicKey }).transaction(), newResolver);
+    expect(fetchConfig(svm, program).pendingUpdate.resolver).to.be.null;
+
+    // Opening the next epoch applies the staged updates, the handover must survive it
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    const result = send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: newResolver.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      newResolver
+    );
+    expect(result.err).to.be.undefined;
+    expect(fetchConfig(svm, program).resolver.toString()).to.equal(newResolver.publicKey.toString());
+  });
+
+  it("Drops an open resolver proposal when the admin stages a resolver update", async () => {
+    const staged = Keypair.generate().publicKey;
+    send(svm, await program.methods.proposeResolver(newResolver.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.updateResolver(staged).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    const config = fetchConfig(svm, program);
+    expect(config.pendingResolver).to.be.null;
+    expect(config.pendingUpdate.resolver.toString()).to.equal(staged.toString());
+
+    const acceptResult = send(svm, await program.methods.acceptResolver().accounts({ config: configPda, signer: newResolver.publicKey }).transaction(), newResolver);
+    expect(typeof acceptResult.err).to.equal("function");
+    expect(acceptResult.meta().logs().join("\n")).to.include("NoPendingResolver");
+  });
+});
@@ -0,0 +1,173 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findConfigPda,
+  findTreasuryPda,
+  findPositionPda,
+  findPoolPda,
+  findEpochResultPda,
+  createAndMintToken2022,
+  EPOCH_DURATION,
+  POSITION_PRICE,
+  WEIGHT_RATE_NUMERATOR,
+  WEIGHT_RATE_DENOMINATOR,
+  send,
+  setupProgram,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Multiple Protocol Instances", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let payer: Keypair;
+  let mintA: PublicKey;
+  let mintB: PublicKey;
+  let configA: PublicKey;
+  let configB: PublicKey;
+
+  const INSTANCE_A = 0;
+  const INSTANCE_B = 1;
+  const POSITION_PRICE_B = 1000 * 10 ** 6;
+
+  async function initInstance(instanceId: number, mint: PublicKey, weightModel: any, positionPrice: number) {
+    const tx = await program.methods
+      .initialize(weightModel, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(positionPrice), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(instanceId))
+      .accounts({
+        signer: payer.publicKey,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, payer);
+  }
+
+  async function initPools(configPda: PublicKey, instanceId: number) {
+    const [poolPda] = findPoolPda(0, 1, program.programId, instanceId);
+    const tx = await program.methods
+      .initializePool(1)
+      .accounts({ config: configPda, signer: payer.publicKey })
+      .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+      .transaction();
+    send(svm, tx, payer);
+    return poolPda;
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin: payer, mint: mintA, configPda: configA } = await setupProgram(false));
+
+    mintB = (await createAndMintToken2022(svm, payer, payer.publicKey, 6)).mint;
+    [configB] = findConfigPda(program.programId, INSTANCE_B);
+
+    await initInstance(INSTANCE_A, mintA, { timeBased: {} }, POSITION_PRICE);
+    // Instance B's mint has 6 decimals, priced at 1000 of its tokens
+    await initInstance(INSTANCE_B, mintB, { constant: {} }, POSITION_PRICE_B);
+  });
+
+  it("Creates isolated configs and treasuries per instance", async () => {
+    const a = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configA)!.data));
+    const b = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configB)!.data));
+
+    expect(a.instanceId.toNumber()).to.equal(INSTANCE_A);
+    expect(b.instanceId.toNumber()).to.equal(INSTANCE_B);
+    expect(a.allowedMint.toString()).to.equal(mintA.toString());
+    expect(b.allowedMint.toString()).to.equal(mintB.toString());
+    expect(a.weightModel).to.deep.equal({ timeBased: {} });
+    expect(b.weightModel).to.deep.equal({ constant: {} });
+    expect(a.positionPrice.toNumber()).to.equal(POSITION_PRICE);
+    expect(b.positionPrice.toNumber()).to.equal(POSITION_PRICE_B);
+
+    const [treasuryA] = findTreasuryPda(program.programId, INSTANCE_A);
+    const [treasuryB] = findTreasuryPda(program.programId, INSTANCE_B);
+    expect(treasuryA.toString()).to.not.equal(treasuryB.toString());
+  });
+
+  it("Rejects re-initializing an existing instance id", async () => {
+    const result = await initInstance(INSTANCE_A, mintB, { constant: {} }, POSITION_PRICE);
+    expect(typeof result.err).to.equal("function");
+  });
+
+  it("Rejects a zero position price", async () => {
+    const mintC = (await createAndMintToken2022(svm, payer, payer.publicKey, 9)).mint;
+    const result = await initInstance(2, mintC, { constant: {} }, 0);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidPositionPrice");
+  });
+
+  it("Rejects a zero epoch duration or weight rate denominator", async () => {
+    const mintC = (await createAndMintToken2022(svm, payer, payer.publicKey, 9)).mint;
+    const cases: [number, number, string][] = [
+      [0, WEIGHT_RATE_DENOMINATOR, "InvalidEpochDuration"],
+      [EPOCH_DURATION, 0, "InvalidWeightRate"],
+    ];
+    for (const [epochDuration, denominator, error] of cases) {
+      const tx = await program.methods
+        .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(epochDuration), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(denominator), new BN(2))
+        .accounts({
+          signer: payer.publicKey,
+          mint: mintC,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction();
+      const result = send(svm, tx, payer);
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include(error);
+    }
+  });
+
+  it("Keeps positions and epochs separate between instances", async () => {
+    const [positionPda] = findPositionPda(payer.publicKey, 0, program.programId, INSTANCE_B);
+    const mintTx = await program.methods
+      .mintPosition()
+      .accounts({
+        config: configB,
+        signer: payer.publicKey,
+        position: positionPda,
+        mint: mintB,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    const mintResult = send(svm, mintTx, payer);
+    expect(mintResult.err).to.be.undefined;
+
+    const a = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configA)!.data));
+    const b = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configB)!.data));
+    expect(a.totalPositionsMinted.toNumber()).to.equal(0);
+    expect(b.totalPositionsMinted.toNumber()).to.equal(1);
+
+    await initPools(configA, INSTANCE_A);
+    expect(svm.getAccount(findEpochResultPda(1, program.programId, INSTANCE_A)[0])).to.not.be.null;
+    expect(svm.getAccount(findEpochResultPda(1, program.programId, INSTANCE_B)[0])).to.be.null;
+  });
+
+  it("Rejects resolving with pools of another instance", async () => {
+    const poolA = await initPools(configA, INSTANCE_A);
+    await initPools(configB, INSTANCE_B);
+
+    const clock = svm.getClock();
+    svm.setClock(
+      new Clock(
+        clock.slot + BigInt(70),
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(70)
+      )
+    );
+
+    const resolveTx = await program.methods
+      .resolve(0)
+      .accounts({
+        signer: payer.publicKey,
+        config: configB,
+      })
+      .remainingAccounts([{ pubkey: poolA, isSigner: false, isWritable: true }])
+      .transaction();
+    const result = send(svm, resolveTx, payer);
+
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidPoolAccount");
+  });
+});
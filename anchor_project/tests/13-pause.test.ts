@@ -0,0 +1,175 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  findPoolPda,
+  findEpochResultPda,
+  getUserPositionCount,
+  send,
+  setupProgram,
+  fetchConfig,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+// Mirrors PAUSE_* flags in constants.rs
+const PAUSE_MINT = 1 << 0;
+const PAUSE_COMMIT = 1 << 1;
+const PAUSE_RESOLVE = 1 << 2;
+const PAUSE_CLAIM = 1 << 3;
+
+describe("Pause", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let guardian: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  async function mintPosition() {
+    const index = getUserPositionCount(svm, admin.publicKey, program.programId, program);
+    const [positionPda] = findPositionPda(admin.publicKey, index, program.programId);
+    const tx = await program.methods
+      .mintPosition()
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        position: positionPda,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  beforeEach(async () => {
+    guardian = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    svm.airdrop(guardian.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+
+    send(svm, await program.methods.setGuardian(guardian.publicKey).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+  });
+
+  it("Guardian pauses minting and only the admin can unpause", async () => {
+    const pauseResult = send(
+      svm,
+      await program.methods.pause(PAUSE_MINT).accounts({ config: configPda, signer: guardian.publicKey }).transaction(),
+      guardian
+    );
+    expect(pauseResult.err).to.be.undefined;
+    expect(fetchConfig(svm, program).paused).to.equal(PAUSE_MINT);
+
+    const pausedMint = await mintPosition();
+    expect(typeof pausedMint.err).to.equal("function");
+    expect(pausedMint.meta().logs().join("\n")).to.include("MintPaused");
+
+    const guardianUnpause = send(
+      svm,
+      await program.methods.unpause(PAUSE_MINT).accountsPartial({ config: configPda, signer: guardian.publicKey }).transaction(),
+      guardian
+    );
+    expect(typeof guardianUnpause.err).to.equal("function");
+    expect(guardianUnpause.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+
+    send(svm, await program.methods.unpause(PAUSE_MINT).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    expect(fetchConfig(svm, program).paused).to.equal(0);
+
+    const resumedMint = await mintPosition();
+    expect(resumedMint.err).to.be.undefined;
+  });
+
+  it("Pausing other flows leaves minting available", async () => {
+    send(
+      svm,
+      await program.methods.pause(PAUSE_COMMIT | PAUSE_RESOLVE | PAUSE_CLAIM).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    expect(fetchConfig(svm, program).paused).to.equal(PAUSE_COMMIT | PAUSE_RESOLVE | PAUSE_CLAIM);
+
+    const result = await mintPosition();
+    expect(result.err).to.be.undefined;
+  });
+
+  it("Pausing resolution also stops opening epochs", async () => {
+    send(svm, await program.methods.pause(PAUSE_RESOLVE).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    const pool = [{ pubkey: poolPda, isSigner: false, isWritable: true }];
+
+    const initPool = send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts(pool)
+        .transaction(),
+      admin
+    );
+    expect(typeof initPool.err).to.equal("function");
+    expect(initPool.meta().logs().join("\n")).to.include("ResolvePaused");
+
+    send(svm, await program.methods.updatePoolsPerEpoch(1).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    const crank = send(
+      svm,
+      await program.methods
+        .crank()
+        .accountsPartial({
+          config: configPda,
+          signer: admin.publicKey,
+          epochResult: findEpochResultPda(1, program.programId)[0],
+        })
+        .remainingAccounts(pool)
+        .transaction(),
+      admin
+    );
+    expect(typeof crank.err).to.equal("function");
+    expect(crank.meta().logs().join("\n")).to.include("ResolvePaused");
+  });
+
+  it("Pausing minting also stops position transfers", async () => {
+    expect((await mintPosition()).err).to.be.undefined;
+    send(svm, await program.methods.setTransferablePositions(true).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, await program.methods.pause(PAUSE_MINT).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    const recipient = Keypair.generate().publicKey;
+    const result = send(
+      svm,
+      await program.methods
+        .transferPosition(new BN(0))
+        .accounts({
+          signer: admin.publicKey,
+          recipient,
+          config: configPda,
+          position: findPositionPda(admin.publicKey, 0, program.programId)[0],
+          newPosition: findPositionPda(recipient, 0, program.programId)[0],
+        })
+        .transaction(),
+      admin
+    );
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("MintPaused");
+  });
+
+  it("Rejects unknown flags and unauthorized signers", async () => {
+    const badFlags = send(
+      svm,
+      await program.methods.pause(1 << 7).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    expect(typeof badFlags.err).to.equal("function");
+    expect(badFlags.meta().logs().join("\n")).to.include("InvalidPauseFlags");
+
+    const stranger = Keypair.generate();
+    svm.airdrop(stranger.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+    const strangerPause = send(
+      svm,
+      await program.methods.pause(PAUSE_MINT).accounts({ config: configPda, signer: stranger.publicKey }).transaction(),
+      stranger
+    );
+    expect(typeof strangerPause.err).to.equal("function");
+    expect(strangerPause.meta().logs().join("\n")).to.include("UnauthorizedGuardian");
+  });
+});
@@ -0,0 +1,156 @@
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findTreasuryPda,
+  findPositionPda,
+  findPoolPda,
+  send,
+  setupProgram,
+  fetchConfig,
+  tokenBalance,
+} from "./utils";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+
+describe("Protocol Fees", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let feeCollector: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+  let treasuryAta: PublicKey;
+  let feeRecipientAta: PublicKey;
+
+  const MINT_FEE_BPS = 100;
+  const CLAIM_FEE_BPS = 250;
+
+  async function withdrawFees(signer: Keypair) {
+    const tx = await program.methods
+      .withdrawFees()
+      .accountsPartial({
+        signer: signer.publicKey,
+        config: configPda,
+        treasuryAta: treasuryAta,
+        feeRecipientAta: feeRecipientAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, signer);
+  }
+
+  beforeEach(async () => {
+    feeCollector = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    const [treasuryPda] = findTreasuryPda(program.programId);
+    treasuryAta = getAssociatedTokenAddressSync(mint, treasuryPda, true, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    feeRecipientAta = getAssociatedTokenAddressSync(mint, feeCollector.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+
+    send(
+      svm,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(admin.publicKey, feeRecipientAta, feeCollector.publicKey, mint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID)
+      ),
+      admin
+    );
+    send(
+      svm,
+      await program.methods.setFeeRecipient().accounts({ config: configPda, signer: admin.publicKey, feeRecipientAta: feeRecipientAta }).transaction(),
+      admin
+    );
+  });
+
+  it("Stages fee rates until the next epoch is opened", async () => {
+    send(svm, await program.methods.updateFees(MINT_FEE_BPS, CLAIM_FEE_BPS).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    let config = fetchConfig(svm, program);
+    expect(config.mintFeeBps).to.equal(0);
+    expect(config.pendingUpdate.mintFeeBps).to.equal(MINT_FEE_BPS);
+
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+
+    config = fetchConfig(svm, program);
+    expect(config.mintFeeBps).to.equal(MINT_FEE_BPS);
+    expect(config.claimFeeBps).to.equal(CLAIM_FEE_BPS);
+  });
+
+  it("Charges the mint fee on top of the price and withdraws only accrued fees", async () => {
+    send(svm, await program.methods.updateFees(MINT_FEE_BPS, CLAIM_FEE_BPS).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+
+    const price = BigInt(fetchConfig(svm, program).positionPrice.toString());
+    const expectedFee = (price * BigInt(MINT_FEE_BPS)) / BigInt(10_000);
+
+    const [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+    const mintResult = send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+    expect(mintResult.err).to.be.undefined;
+    expect(tokenBalance(svm, treasuryAta)).to.equal(price + expectedFee);
+    expect(fetchConfig(svm, program).accruedFees.toString()).to.equal(expectedFee.toString());
+
+    const withdrawResult = await withdrawFees(admin);
+    expect(withdrawResult.err).to.be.undefined;
+    expect(tokenBalance(svm, feeRecipientAta)).to.equal(expectedFee);
+    expect(tokenBalance(svm, treasuryAta)).to.equal(price);
+    expect(fetchConfig(svm, program).accruedFees.toNumber()).to.equal(0);
+
+    const emptyWithdraw = await withdrawFees(admin);
+    expect(typeof emptyWithdraw.err).to.equal("function");
+    expect(emptyWithdraw.meta().logs().join("\n")).to.include("NoFeesToWithdraw");
+  });
+
+  it("Rejects fees above the maximum and non-admin withdrawals", async () => {
+    const tooHigh = send(
+      svm,
+      await program.methods.updateFees(10_001, 0).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    expect(typeof tooHigh.err).to.equal("function");
+    expect(tooHigh.meta().logs().join("\n")).to.include("InvalidFee");
+
+    svm.airdrop(feeCollector.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+    const strangerWithdraw = await withdrawFees(feeCollector);
+    expect(typeof strangerWithdraw.err).to.equal("function");
+    expect(strangerWithdraw.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+  });
+});
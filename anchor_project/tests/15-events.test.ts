@@ -0,0 +1,104 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  findPoolPda,
+  send,
+  setupProgram,
+  decodeCpiEvents,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Events", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  // emit_cpi! events travel as self-CPI data: 8 byte event ix tag + event
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+  });
+
+  it("Emits PositionMinted", async () => {
+    const [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+    const result = send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+
+    const [event] = decodeCpiEvents(program, result);
+    expect(event.name).to.equal("positionMinted");
+    expect(event.data.owner.toString()).to.equal(admin.publicKey.toString());
+    expect(event.data.globalId.toNumber()).to.equal(0);
+  });
+
+  it("Emits PoolsInitialized", async () => {
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    const result = send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+
+    const [event] = decodeCpiEvents(program, result);
+    expect(event.name).to.equal("poolsInitialized");
+    expect(event.data.epoch.toNumber()).to.equal(1);
+    expect(event.data.poolCount).to.equal(1);
+  });
+
+  it("Emits ConfigUpdated for staged changes", async () => {
+    const result = send(
+      svm,
+      await program.methods.updatePositionPrice(new BN(42)).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+
+    const [event] = decodeCpiEvents(program, result);
+    expect(event.name).to.equal("configUpdated");
+    expect(event.data.staged).to.be.true;
+    expect(event.data.change.positionPrice.positionPrice.toNumber()).to.equal(42);
+  });
+
+  it("Tags authority and pause events with their config", async () => {
+    const newAdmin = Keypair.generate().publicKey;
+    const proposeResult = send(
+      svm,
+      await program.methods.proposeAdmin(newAdmin).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    const [proposed] = decodeCpiEvents(program, proposeResult);
+    expect(proposed.name).to.equal("authorityProposed");
+    expect(proposed.data.config.toBase58()).to.equal(configPda.toBase58());
+
+    const pauseResult = send(
+      svm,
+      await program.methods.pause(1).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    const [paused] = decodeCpiEvents(program, pauseResult);
+    expect(paused.name).to.equal("pauseUpdated");
+    expect(paused.data.config.toBase58()).to.equal(configPda.toBase58());
+  });
+});
@@ -0,0 +1,68 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findEpochResultPda,
+  send,
+  setupProgram,
+  commitFirstPosition,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Claim State Guard", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  const EPOCH = 1;
+  const POOL_ID = 0;
+
+  async function claim() {
+    const tx = await program.methods
+      .claim(POOL_ID, new BN(EPOCH))
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  // Rewrite the stored state, e.g. to reach Pending without a live VRF oracle
+  async function setEpochResultState(state: any) {
+    const [epochResultPda] = findEpochResultPda(EPOCH, program.programId);
+    const account = svm.getAccount(epochResultPda)!;
+    const epochResult = program.coder.accounts.decode("epochResult", Buffer.from(account.data));
+    epochResult.epochResultState = state;
+    const data = await program.coder.accounts.encode("epochResult", epochResult);
+    svm.setAccount(epochResultPda, { ...account, data });
+  }
+
+  beforeEach(async () => {
+    const setup = await setupProgram();
+    ({ svm, program, admin, mint, configPda } = setup);
+
+    const commitResult = await commitFirstPosition(setup, POOL_ID, EPOCH);
+    expect(commitResult.err).to.be.undefined;
+  });
+
+  it("Rejects claims while the epoch is Active", async () => {
+    const result = await claim();
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("EpochStillActive");
+  });
+
+  it("Rejects claims while resolution is Pending", async () => {
+    await setEpochResultState({ pending: {} });
+
+    const result = await claim();
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("EpochResolutionPending");
+  });
+});
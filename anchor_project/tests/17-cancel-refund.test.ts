@@ -0,0 +1,187 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPoolPda,
+  findCommitmentPda,
+  findEpochResultPda,
+  send,
+  setupProgram,
+  commitFirstPosition,
+  fetchEpochResult,
+  tokenBalance,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+// Mirrors PAUSE_CLAIM in constants.rs
+const PAUSE_CLAIM = 1 << 3;
+
+describe("Epoch Cancellation and Refunds", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+  let userAta: PublicKey;
+
+  const EPOCH = 1;
+  const POOL_ID = 0;
+
+  async function cancelEpoch(signer: Keypair) {
+    const [poolPda] = findPoolPda(POOL_ID, EPOCH, program.programId);
+    const tx = await program.methods
+      .cancelEpoch(new BN(EPOCH))
+      .accountsPartial({ config: configPda, signer: signer.publicKey })
+      .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+      .transaction();
+    return send(svm, tx, signer);
+  }
+
+  async function refund() {
+    const tx = await program.methods
+      .refund(POOL_ID, new BN(EPOCH))
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  async function claim() {
+    const tx = await program.methods
+      .claim(POOL_ID, new BN(EPOCH))
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  // Rewrite the stored state to reach Pending without a live VRF oracle
+  async function setEpochResultState(state: any) {
+    const [epochResultPda] = findEpochResultPda(EPOCH, program.programId);
+    const account = svm.getAccount(epochResultPda)!;
+    const epochResult = program.coder.accounts.decode("epochResult", Buffer.from(account.data));
+    epochResult.epochResultState = state;
+    const data = await program.coder.accounts.encode("epochResult", epochResult);
+    svm.setAccount(epochResultPda, { ...account, data });
+  }
+
+  beforeEach(async () => {
+    const setup = await setupProgram();
+    ({ svm, program, admin, mint, configPda, adminAta: userAta } = setup);
+
+    const commitResult = await commitFirstPosition(setup, POOL_ID, EPOCH);
+    expect(commitResult.err).to.be.undefined;
+  });
+
+  it("Cancels an Active epoch and refunds the committed stake", async () => {
+    const cancelResult = await cancelEpoch(admin);
+    expect(cancelResult.err).to.be.undefined;
+    expect(fetchEpochResult(svm, program, EPOCH).epochResultState).to.deep.equal({ cancelled: {} });
+
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    expect(config.currentEpoch.toNumber()).to.equal(EPOCH + 1);
+    expect(svm.getAccount(findPoolPda(POOL_ID, EPOCH, program.programId)[0])).to.be.null;
+
+    const claimResult = await claim();
+    expect(typeof claimResult.err).to.equal("function");
+    expect(claimResult.meta().logs().join("\n")).to.include("EpochNotResolved");
+
+    const balanceBefore = tokenBalance(svm, userAta);
+    const refundResult = await refund();
+    expect(refundResult.err).to.be.undefined;
+    expect(tokenBalance(svm, userAta) - balanceBefore).to.equal(BigInt(config.positionPrice.toString()));
+
+    const [commitmentPda] = findCommitmentPda(admin.publicKey, POOL_ID, EPOCH, program.programId);
+    expect(svm.getAccount(commitmentPda)).to.be.null;
+
+    svm.expireBlockhash();
+    const secondRefund = await refund();
+    expect(typeof secondRefund.err).to.equal("function");
+  });
+
+  it("Rejects cancelling an Active epoch without all of its pools", async () => {
+    const tx = await program.methods
+      .cancelEpoch(new BN(EPOCH))
+      .accountsPartial({ config: configPda, signer: admin.publicKey })
+      .transaction();
+    const result = send(svm, tx, admin);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidPoolAccount");
+    expect(fetchEpochResult(svm, program, EPOCH).epochResultState).to.deep.equal({ active: {} });
+  });
+
+  it("Cancels a Pending epoch", async () => {
+    await setEpochResultState({ pending: {} });
+
+    const cancelResult = await cancelEpoch(admin);
+    expect(cancelResult.err).to.be.undefined;
+    expect(fetchEpochResult(svm, program, EPOCH).epochResultState).to.deep.equal({ cancelled: {} });
+
+    const refundResult = await refund();
+    expect(refundResult.err).to.be.undefined;
+  });
+
+  it("Refunds the price paid, not the epoch's position price", async () => {
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    const pricePaid = BigInt(config.positionPrice.toString());
+
+    const [commitmentPda] = findCommitmentPda(admin.publicKey, POOL_ID, EPOCH, program.programId);
+    const commitment = program.coder.accounts.decode("commitment", Buffer.from(svm.getAccount(commitmentPda)!.data));
+    expect(BigInt(commitment.positionValue.toString())).to.equal(pricePaid);
+
+    // A different snapshot on the epoch must not change what comes back
+    const [epochResultPda] = findEpochResultPda(EPOCH, program.programId);
+    const account = svm.getAccount(epochResultPda)!;
+    const epochResult = program.coder.accounts.decode("epochResult", Buffer.from(account.data));
+    epochResult.positionPrice = epochResult.positionPrice.mul(new BN(2));
+    svm.setAccount(epochResultPda, { ...account, data: await program.coder.accounts.encode("epochResult", epochResult) });
+
+    expect((await cancelEpoch(admin)).err).to.be.undefined;
+    const balanceBefore = tokenBalance(svm, userAta);
+    expect((await refund()).err).to.be.undefined;
+    expect(tokenBalance(svm, userAta) - balanceBefore).to.equal(pricePaid);
+  });
+
+  it("Refunds stop while claims are paused", async () => {
+    expect((await cancelEpoch(admin)).err).to.be.undefined;
+    send(svm, await program.methods.pause(PAUSE_CLAIM).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    const paused = await refund();
+    expect(typeof paused.err).to.equal("function");
+    expect(paused.meta().logs().join("\n")).to.include("ClaimPaused");
+
+    send(svm, await program.methods.unpause(PAUSE_CLAIM).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    svm.expireBlockhash();
+    expect((await refund()).err).to.be.undefined;
+  });
+
+  it("Rejects refunds before cancellation and cancelling a resolved epoch", async () => {
+    const refundResult = await refund();
+    expect(typeof refundResult.err).to.equal("function");
+    expect(refundResult.meta().logs().join("\n")).to.include("EpochNotCancelled");
+
+    await setEpochResultState({ resolved: {} });
+    const cancelResult = await cancelEpoch(admin);
+    expect(typeof cancelResult.err).to.equal("function");
+    expect(cancelResult.meta().logs().join("\n")).to.include("EpochNotCancellable");
+  });
+
+  it("Only the admin can cancel", async () => {
+    const stranger = Keypair.generate();
+    svm.airdrop(stranger.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+
+    const result = await cancelEpoch(stranger);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("UnauthorizedAdmin");
+  });
+});
@@ -0,0 +1,141 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findEpochResultPda,
+  send,
+  setupProgram,
+  commitFirstPosition,
+  fetchEpochResult,
+} from "./utils";
+
+describe("VRF Timeout Fallback", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  const EPOCH = 1;
+  const POOL_ID = 0;
+
+  // Put the epoch in Pending as if randomness was requested now and the oracle never answered
+  async function markPending(edit: (epochResult: any) => void = () => {}) {
+    const [epochResultPda] = findEpochResultPda(EPOCH, program.programId);
+    const account = svm.getAccount(epochResultPda)!;
+    const epochResult = program.coder.accounts.decode("epochResult", Buffer.from(account.data));
+    epochResult.epochResultState = { pending: {} };
+    epochResult.requestedAt = new BN(svm.getClock().unixTimestamp.toString());
+    epochResult.totalPositionAmount = new BN(1);
+    epochResult.poolWeights[POOL_ID] = new BN(1);
+    edit(epochResult);
+    const data = await program.coder.accounts.encode("epochResult", epochResult);
+    svm.setAccount(epochResultPda, { ...account, data });
+  }
+
+  function advanceClock(seconds: number) {
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(seconds))
+    );
+  }
+
+  async function fallbackResolve() {
+    const tx = await program.methods
+      .fallbackResolve(new BN(EPOCH))
+      .accounts({ config: configPda, signer: admin.publicKey })
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  beforeEach(async () => {
+    const setup = await setupProgram();
+    ({ svm, program, admin, mint, configPda } = setup);
+
+    const commitResult = await commitFirstPosition(setup, POOL_ID, EPOCH);
+    expect(commitResult.err).to.be.undefined;
+  });
+
+  it("Rejects recovery before the timeout", async () => {
+    await markPending();
+
+    const fallbackResult = await fallbackResolve();
+    expect(typeof fallbackResult.err).to.equal("function");
+    expect(fallbackResult.meta().logs().join("\n")).to.include("VrfTimeoutNotReached");
+
+    const retryResult = send(
+      svm,
+      await program.methods
+        .retryResolve(new BN(EPOCH), 0)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .transaction(),
+      admin
+    );
+    expect(typeof retryResult.err).to.equal("function");
+    expect(retryResult.meta().logs().join("\n")).to.include("VrfTimeoutNotReached");
+  });
+
+  it("Resolves from a slot hash once the timeout has passed", async () => {
+    await markPending();
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    advanceClock(config.vrfTimeout.toNumber());
+
+    const result = await fallbackResolve();
+    expect(result.err).to.be.undefined;
+
+    const epochResult = fetchEpochResult(svm, program, EPOCH);
+    expect(epochResult.epochResultState).to.deep.equal({ resolved: {} });
+    expect(epochResult.resolutionPath).to.deep.equal({ slotHashFallback: {} });
+    expect(epochResult.winningPoolId).to.equal(POOL_ID);
+  });
+
+  it("Adds a winnerless epoch to the carry over instead of replacing it", async () => {
+    // Carry over left by a later epoch that resolved while this one was Pending
+    const account = svm.getAccount(configPda)!;
+    const config = program.coder.accounts.decode("config", Buffer.from(account.data));
+    config.remainingTotalPosition = new BN(2);
+    config.remainingTotalValue = new BN(2000);
+    svm.setAccount(configPda, { ...account, data: await program.coder.accounts.encode("config", config) });
+
+    await markPending((epochResult) => {
+      epochResult.poolWeights[POOL_ID] = new BN(0);
+      epochResult.totalValue = new BN(1000);
+    });
+    advanceClock(config.vrfTimeout.toNumber());
+    expect((await fallbackResolve()).err).to.be.undefined;
+
+    const after = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    expect(after.remainingTotalPosition.toNumber()).to.equal(3);
+    expect(after.remainingTotalValue.toNumber()).to.equal(3000);
+  });
+
+  it("Rejects the fallback for an epoch without pools", async () => {
+    await markPending((epochResult) => {
+      epochResult.poolCount = 0;
+    });
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    advanceClock(config.vrfTimeout.toNumber());
+
+    const result = await fallbackResolve();
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("NoPoolsProvided");
+  });
+
+  it("Only touches Pending epochs", async () => {
+    const result = await fallbackResolve();
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("EpochNotPending");
+  });
+
+  it("Lets the admin change the timeout", async () => {
+    send(svm, await program.methods.updateVrfTimeout(new BN(10)).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    expect(config.vrfTimeout.toNumber()).to.equal(10);
+
+    const invalid = send(svm, await program.methods.updateVrfTimeout(new BN(0)).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    expect(typeof invalid.err).to.equal("function");
+    expect(invalid.meta().logs().join("\n")).to.include("InvalidVrfTimeout");
+  });
+});
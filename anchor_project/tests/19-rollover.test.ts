@@ -0,0 +1,204 @@
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPoolPda,
+  findEpochResultPda,
+  EPOCH_DURATION,
+  send,
+  setupProgram,
+  fetchEpochResult,
+} from "./utils";
+
+describe("Automatic Epoch Rollover", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let cranker: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  const POOLS_PER_EPOCH = 2;
+
+  function poolAccounts(epoch: number) {
+    return Array.from({ length: POOLS_PER_EPOCH }, (_, id) => ({
+      pubkey: findPoolPda(id, epoch, program.programId)[0],
+      isSigner: false,
+      isWritable: true,
+    }));
+  }
+
+  async function crankIx(epoch: number) {
+    return program.methods
+      .crank()
+      .accountsPartial({
+        config: configPda,
+        signer: cranker.publicKey,
+        epochResult: findEpochResultPda(epoch, program.programId)[0],
+      })
+      .remainingAccounts(poolAccounts(epoch))
+      .instruction();
+  }
+
+  beforeEach(async () => {
+    cranker = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    svm.airdrop(cranker.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+  });
+
+  it("Rejects the crank while rollover is disabled", async () => {
+    const result = send(svm, new Transaction().add(await crankIx(1)), cranker);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("AutoRolloverDisabled");
+  });
+
+  it("Opens the next epoch in the same transaction as resolve", async () => {
+    send(svm, await program.methods.updatePoolsPerEpoch(POOLS_PER_EPOCH).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    // Anyone can open the first epoch
+    const firstCrank = send(svm, new Transaction().add(await crankIx(1)), cranker);
+    expect(firstCrank.err).to.be.undefined;
+    expect(fetchEpochResult(svm, program, 1).poolCount).to.equal(POOLS_PER_EPOCH);
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(EPOCH_DURATION + 1))
+    );
+
+    const resolveIx = await program.methods
+      .resolve(0)
+      .accounts({ config: configPda, signer: admin.publicKey })
+      .remainingAccounts(poolAccounts(1))
+      .instruction();
+    const tx = new Transaction().add(resolveIx, await crankIx(2));
+    tx.recentBlockhash = svm.latestBlockhash();
+    tx.feePayer = admin.publicKey;
+    tx.sign(admin, cranker);
+    const result: any = svm.sendTransaction(tx);
+    expect(result.err).to.be.undefined;
+
+    expect(fetchEpochResult(svm, program, 1).epochResultState).to.deep.equal({ resolved: {} });
+    const next = fetchEpochResult(svm, program, 2);
+    expect(next.epochResultState).to.deep.equal({ active: {} });
+    expect(next.poolCount).to.equal(POOLS_PER_EPOCH);
+    for (const pool of poolAccounts(2)) {
+      expect(svm.getAccount(pool.pubkey)).to.not.be.null;
+    }
+  });
+
+  it("Opens an epoch whose pool addresses were funded beforehand", async () => {
+    send(svm, await program.methods.updatePoolsPerEpoch(POOLS_PER_EPOCH).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+
+    // Lamports sent to a predictable pool PDA must not block the epoch from opening
+    const [prefunded] = poolAccounts(1);
+    svm.setAccount(prefunded.pubkey, {
+      lamports: 1,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+
+    const result = send(svm, new Transaction().add(await crankIx(1)), cranker);
+    expect(result.err).to.be.undefined;
+
+    const pool = program.coder.accounts.decode("pool", Buffer.from(svm.getAccount(prefunded.pubkey)!.data));
+    expect(pool.id).to.equal(0);
+    expect(pool.epoch.toNumber()).to.equal(1);
+    expect(svm.getAccount(prefunded.pubkey)!.owner.toBase58()).to.equal(program.programId.toBase58());
+  });
+
+  async function rolloverTx(rentPayer: PublicKey, winningPoolId: number = 0) {
+    return program.methods
+      .rollover(winningPoolId)
+      .accountsPartial({
+        config: configPda,
+        signer: admin.publicKey,
+        rentPayer,
+        epochResult: findEpochResultPda(1, program.programId)[0],
+        nextEpochResult: findEpochResultPda(2, program.programId)[0],
+      })
+      .remainingAccounts([...poolAccounts(1), ...poolAccounts(2)])
+      .transaction();
+  }
+
+  it("Resolves and opens the next epoch in one instruction, pool rent back to the cranker", async () => {
+    send(svm, await program.methods.updatePoolsPerEpoch(POOLS_PER_EPOCH).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    expect(send(svm, new Transaction().add(await crankIx(1)), cranker).err).to.be.undefined;
+    expect(fetchEpochResult(svm, program, 1).rentPayer.toBase58()).to.equal(cranker.publicKey.toBase58());
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(EPOCH_DURATION + 1))
+    );
+
+    const poolRent = poolAccounts(1).reduce((sum, pool) => sum + svm.getAccount(pool.pubkey)!.lamports, 0);
+    const crankerBefore = svm.getBalance(cranker.publicKey)!;
+
+    const result = send(svm, await rolloverTx(cranker.publicKey), admin);
+    expect(result.err).to.be.undefined;
+
+    expect(fetchEpochResult(svm, program, 1).epochResultState).to.deep.equal({ resolved: {} });
+    const next = fetchEpochResult(svm, program, 2);
+    expect(next.epochResultState).to.deep.equal({ active: {} });
+    expect(next.rentPayer.toBase58()).to.equal(admin.publicKey.toBase58());
+    for (const pool of poolAccounts(2)) {
+      expect(svm.getAccount(pool.pubkey)).to.not.be.null;
+    }
+    expect(Number(svm.getBalance(cranker.publicKey)! - crankerBefore)).to.equal(poolRent);
+  });
+
+  it("Rejects a rollover that sends the pool rent elsewhere", async () => {
+    send(svm, await program.methods.updatePoolsPerEpoch(POOLS_PER_EPOCH).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, new Transaction().add(await crankIx(1)), cranker);
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(EPOCH_DURATION + 1))
+    );
+
+    const result = send(svm, await rolloverTx(admin.publicKey), admin);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidRentPayer");
+  });
+
+  it("Rejects a winning pool outside the epoch on resolve and rollover", async () => {
+    send(svm, await program.methods.updatePoolsPerEpoch(POOLS_PER_EPOCH).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    send(svm, new Transaction().add(await crankIx(1)), cranker);
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(EPOCH_DURATION + 1))
+    );
+
+    const resolveResult = send(
+      svm,
+      await program.methods
+        .resolve(POOLS_PER_EPOCH)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts(poolAccounts(1))
+        .transaction(),
+      admin
+    );
+    expect(typeof resolveResult.err).to.equal("function");
+    expect(resolveResult.meta().logs().join("\n")).to.include("InvalidPoolId");
+
+    const rolloverResult = send(svm, await rolloverTx(cranker.publicKey, 255), admin);
+    expect(typeof rolloverResult.err).to.equal("function");
+    expect(rolloverResult.meta().logs().join("\n")).to.include("InvalidPoolId");
+    expect(fetchEpochResult(svm, program, 1).epochResultState).to.deep.equal({ active: {} });
+  });
+
+  it("Rejects a pool count above the maximum", async () => {
+    const result = send(
+      svm,
+      await program.methods.updatePoolsPerEpoch(255).accounts({ config: configPda, signer: admin.publicKey }).transaction(),
+      admin
+    );
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("TooManyPools");
+  });
+});
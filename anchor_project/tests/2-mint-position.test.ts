@@ -5,7 +5,7 @@ import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
 import { LiteSVM, Clock } from "litesvm";
 import { expect } from "chai";
 import { AnchorProject } from "../target/types/anchor_project";
-import { findConfigPda, findPositionPda, EPOCH_DURATION, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR } from "./utils";
+import { findConfigPda, findPositionPda, EPOCH_DURATION, POSITION_PRICE, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR, INSTANCE_ID } from "./utils";
 import { createAndMintToken2022 } from "./utils";
 
 import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
@@ -59,7 +59,7 @@ describe("Mint Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -83,6 +83,7 @@ describe("Mint Position", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
@@ -131,7 +132,7 @@ describe("Mint Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -153,6 +154,7 @@ describe("Mint Position", () => {
     const mintPosition1Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position1Pda,
         mint: mint,
@@ -180,6 +182,7 @@ describe("Mint Position", () => {
     const mintPosition2Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position2Pda,
         mint: mint,
@@ -213,7 +216,7 @@ describe("Mint Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -230,6 +233,7 @@ describe("Mint Position", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
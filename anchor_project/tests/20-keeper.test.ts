@@ -0,0 +1,99 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPoolPda,
+  EPOCH_DURATION,
+  POSITION_PRICE,
+  WEIGHT_RATE_NUMERATOR,
+  WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
+  send,
+  setupProgram,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Keeper Resolution", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let keeper: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+
+  async function setup(resolutionType: any) {
+    send(
+      svm,
+      await program.methods
+        .initialize({ timeBased: {} }, resolutionType, admin.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
+        .accounts({
+          signer: admin.publicKey,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+
+    const [poolPda] = findPoolPda(0, 1, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(EPOCH_DURATION + 1))
+    );
+    return poolPda;
+  }
+
+  async function resolveAs(signer: Keypair, poolPda: PublicKey) {
+    const tx = await program.methods
+      .resolve(0)
+      .accounts({ config: configPda, signer: signer.publicKey })
+      .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+      .transaction();
+    return send(svm, tx, signer);
+  }
+
+  beforeEach(async () => {
+    keeper = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram(false));
+
+    svm.airdrop(keeper.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+  });
+
+  it("Keeps Admin resolution restricted to the resolver", async () => {
+    const poolPda = await setup({ admin: {} });
+
+    const result = await resolveAs(keeper, poolPda);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("UnauthorizedResolver");
+  });
+
+  it("Lets any keeper trigger Oracle resolution", async () => {
+    const poolPda = await setup({ oracle: {} });
+
+    // The VRF program is only mocked here, so the request itself cannot succeed,
+    // but the keeper must get past the resolver check
+    const result = await resolveAs(keeper, poolPda);
+    expect(result.meta().logs().join("\n")).to.not.include("UnauthorizedResolver");
+  });
+
+  it("Lets the admin configure the keeper tip", async () => {
+    await setup({ oracle: {} });
+
+    send(svm, await program.methods.updateKeeperTip(new BN(5_000)).accounts({ config: configPda, signer: admin.publicKey }).transaction(), admin);
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    expect(config.keeperTip.toNumber()).to.equal(5_000);
+  });
+});
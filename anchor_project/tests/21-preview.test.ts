@@ -0,0 +1,103 @@
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  findPoolPda,
+  send,
+  setupProgram,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Weight Preview", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+  let positionPda: PublicKey;
+  let poolPdas: PublicKey[];
+
+  const NUM_POOLS = 2;
+  const AGE = 30;
+
+  function simulate(tx: Transaction, signer: Keypair): any {
+    tx.recentBlockhash = svm.latestBlockhash();
+    tx.feePayer = signer.publicKey;
+    tx.sign(signer);
+    return svm.simulateTransaction(tx);
+  }
+
+  async function previewTx(pools: PublicKey[]) {
+    return program.methods
+      .previewWeight()
+      .accounts({ config: configPda, position: positionPda })
+      .remainingAccounts(pools.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .transaction();
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    poolPdas = [...Array(NUM_POOLS).keys()].map((id) => findPoolPda(id, 1, program.programId)[0]);
+    send(
+      svm,
+      await program.methods
+        .initializePool(NUM_POOLS)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts(poolPdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+        .transaction(),
+      admin
+    );
+
+    [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+    send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(AGE))
+    );
+  });
+
+  it("Returns the on-chain weight and projected reward per pool", async () => {
+    const result = simulate(await previewTx(poolPdas), admin);
+    expect(typeof result.err).to.not.equal("function");
+
+    const preview = program.coder.types.decode(
+      "weightPreview",
+      Buffer.from(result.meta().returnData().data())
+    );
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+
+    // TimeBased at 1 weight per second, scaled by WEIGHT_PRECISION
+    expect(preview.weight.toNumber()).to.equal(AGE * 10_000);
+    expect(preview.pools.length).to.equal(NUM_POOLS);
+    for (const [id, pool] of preview.pools.entries()) {
+      expect(pool.poolId).to.equal(id);
+      // Empty pools: the position would hold all the weight and win the whole pot
+      expect(pool.shareBps.toNumber()).to.equal(10_000);
+      expect(pool.projectedReward.toString()).to.equal(config.positionPrice.toString());
+    }
+  });
+
+  it("Rejects a preview without every pool of the epoch", async () => {
+    const result = simulate(await previewTx(poolPdas.slice(1)), admin);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidPoolAccount");
+  });
+});
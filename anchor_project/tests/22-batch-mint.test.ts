@@ -0,0 +1,112 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  positionAccountMetas,
+  getUserPositionCount,
+  send,
+  setupProgram,
+  tokenBalance,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Batch Mint", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let userAta: PublicKey;
+  let configPda: PublicKey;
+
+  async function mintPositions(count: number, firstIndex: number, accounts: number = count) {
+    const positions = [...Array(accounts).keys()].map(
+      (i) => findPositionPda(admin.publicKey, firstIndex + i, program.programId)[0]
+    );
+    const tx = await program.methods
+      .mintPositions(count)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [...Array(accounts).keys()].flatMap((i) => positionAccountMetas(admin.publicKey, firstIndex + i, program.programId))
+      )
+      .transaction();
+    return { result: send(svm, tx, admin), positions };
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda, adminAta: userAta } = await setupProgram());
+  });
+
+  it("Mints several positions with a single transfer", async () => {
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    const price = BigInt(config.positionPrice.toString());
+    const balanceBefore = tokenBalance(svm, userAta);
+
+    const { result, positions } = await mintPositions(3, 0);
+    expect(result.err).to.be.undefined;
+
+    expect(balanceBefore - tokenBalance(svm, userAta)).to.equal(price * BigInt(3));
+    expect(getUserPositionCount(svm, admin.publicKey, program.programId, program)).to.equal(3);
+    for (const [i, pda] of positions.entries()) {
+      const position = program.coder.accounts.decode("positionAccount", Buffer.from(svm.getAccount(pda)!.data));
+      expect(position.owner.toBase58()).to.equal(admin.publicKey.toBase58());
+      expect(position.userIndex.toNumber()).to.equal(i);
+      expect(position.globalId.toNumber()).to.equal(i);
+    }
+
+    // Continues from the user's position count
+    const second = await mintPositions(2, 3);
+    expect(second.result.err).to.be.undefined;
+    expect(getUserPositionCount(svm, admin.publicKey, program.programId, program)).to.equal(5);
+  });
+
+  it("Mints into position addresses that were funded beforehand", async () => {
+    // Lamports sent to a predictable PDA must not block minting into it
+    const [positionPda] = findPositionPda(admin.publicKey, 1, program.programId);
+    svm.setAccount(positionPda, {
+      lamports: 1,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+
+    const { result, positions } = await mintPositions(2, 0);
+    expect(result.err).to.be.undefined;
+    const position = program.coder.accounts.decode("positionAccount", Buffer.from(svm.getAccount(positions[1])!.data));
+    expect(position.userIndex.toNumber()).to.equal(1);
+    expect(svm.getAccount(positions[1])!.owner.toBase58()).to.equal(program.programId.toBase58());
+  });
+
+  it("Rejects a count of zero or above the limit", async () => {
+    for (const count of [0, 6]) {
+      const { result } = await mintPositions(count, 0, Math.max(count, 1));
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("InvalidPositionCount");
+    }
+  });
+
+  it("Rejects position accounts that are out of order", async () => {
+    const tx = await program.methods
+      .mintPositions(2)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([1, 0].flatMap((i) => positionAccountMetas(admin.publicKey, i, program.programId)))
+      .transaction();
+    const result = send(svm, tx, admin);
+    expect(typeof result.err).to.equal("function");
+  });
+});
@@ -0,0 +1,147 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, Keypair, PublicKey, Transaction, ComputeBudgetProgram } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  positionAccountMetas,
+  findPoolPda,
+  findCommitmentPda,
+  decodeCpiEvents,
+  send,
+  setupProgram,
+} from "./utils";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+  createTransferCheckedInstruction,
+} from "@solana/spl-token";
+
+describe("Batch Commit", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let adminAta: PublicKey;
+  let configPda: PublicKey;
+
+  const POOL_ID = 0;
+  const EPOCH = 1;
+  const AGE = 10;
+
+  async function mintPositions(user: Keypair, count: number) {
+    const positions = [...Array(count).keys()].map((i) => positionAccountMetas(user.publicKey, i, program.programId));
+    send(
+      svm,
+      await program.methods
+        .mintPositions(count)
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+        .accounts({
+          config: configPda,
+          signer: user.publicKey,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(positions.flat())
+        .transaction(),
+      user
+    );
+    return positions;
+  }
+
+  async function commitMany(user: Keypair, positions: anchor.web3.AccountMeta[][]) {
+    const [commitmentPda] = findCommitmentPda(user.publicKey, POOL_ID, EPOCH, program.programId);
+    const tx = await program.methods
+      .commitMany(POOL_ID)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .accounts({
+        config: configPda,
+        signer: user.publicKey,
+        commitment: commitmentPda,
+      })
+      .remainingAccounts(positions.flat())
+      .transaction();
+    return send(svm, tx, user);
+  }
+
+  function advance(seconds: number) {
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(seconds))
+    );
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda, adminAta } = await setupProgram());
+
+    const [poolPda] = findPoolPda(POOL_ID, EPOCH, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+  });
+
+  it("Commits several positions into one commitment", async () => {
+    const positions = await mintPositions(admin, 3);
+    advance(AGE);
+
+    const result = await commitMany(admin, positions);
+    expect(result.err).to.be.undefined;
+
+    const [commitmentPda] = findCommitmentPda(admin.publicKey, POOL_ID, EPOCH, program.programId);
+    const commitment = program.coder.accounts.decode("commitment", Buffer.from(svm.getAccount(commitmentPda)!.data));
+    expect(commitment.positionAmount.toNumber()).to.equal(3);
+    expect(commitment.weight.toNumber()).to.equal(3 * AGE * 10_000);
+
+    const [poolPda] = findPoolPda(POOL_ID, EPOCH, program.programId);
+    const pool = program.coder.accounts.decode("pool", Buffer.from(svm.getAccount(poolPda)!.data));
+    expect(pool.totalPositions.toNumber()).to.equal(3);
+    expect(pool.totalWeight.toNumber()).to.equal(3 * AGE * 10_000);
+
+    // Each Committed event carries the pool totals right after that position
+    const events = decodeCpiEvents(program, result).filter((event) => event.name === "committed");
+    expect(events.map((event) => event.data.poolTotalPositions.toNumber())).to.deep.equal([1, 2, 3]);
+    expect(events.map((event) => event.data.poolTotalWeight.toNumber())).to.deep.equal([1, 2, 3].map((n) => n * AGE * 10_000));
+
+    // Position accounts, their mints and token accounts are all closed
+    for (const meta of positions.flat()) {
+      const info = svm.getAccount(meta.pubkey);
+      expect(info === null || info.lamports === 0).to.be.true;
+    }
+  });
+
+  it("Rejects positions owned by someone else", async () => {
+    const other = Keypair.generate();
+    svm.airdrop(other.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+    const otherAta = getAssociatedTokenAddressSync(mint, other.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    send(
+      svm,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(admin.publicKey, otherAta, other.publicKey, mint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createTransferCheckedInstruction(adminAta, mint, otherAta, admin.publicKey, BigInt(10_000) * BigInt(10 ** 9), 9, [], TOKEN_2022_PROGRAM_ID)
+      ),
+      admin
+    );
+
+    const [ownPosition] = await mintPositions(admin, 1);
+    const [otherPosition] = await mintPositions(other, 1);
+
+    const result = await commitMany(admin, [ownPosition, otherPosition]);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidPositionAccount");
+  });
+
+  it("Rejects an empty batch", async () => {
+    const result = await commitMany(admin, []);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("NotEnoughAccounts");
+  });
+});
@@ -0,0 +1,155 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
+import { LiteSVM, Clock } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  positionAccountMetas,
+  findPoolPda,
+  findCommitmentPda,
+  findEpochResultPda,
+  EPOCH_DURATION,
+  send,
+  setupProgram,
+  tokenBalance,
+} from "./utils";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Batch Claim", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let adminAta: PublicKey;
+  let configPda: PublicKey;
+  let price: bigint;
+
+  const POOL_ID = 0;
+
+  function advance(seconds: number) {
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(seconds))
+    );
+  }
+
+  // Open the epoch with one pool, commit one position to it and resolve it as the winner
+  async function playEpoch(epoch: number, positionId: number) {
+    const [poolPda] = findPoolPda(POOL_ID, epoch, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+
+    const [positionPda] = findPositionPda(admin.publicKey, positionId, program.programId);
+    const [commitmentPda] = findCommitmentPda(admin.publicKey, POOL_ID, epoch, program.programId);
+    send(
+      svm,
+      await program.methods
+        .commit(new BN(positionId), POOL_ID)
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          commitment: commitmentPda,
+        })
+        .transaction(),
+      admin
+    );
+
+    advance(EPOCH_DURATION + 1);
+    send(
+      svm,
+      await program.methods
+        .resolve(POOL_ID)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+  }
+
+  function pair(commitmentEpoch: number, epochResultEpoch: number) {
+    return [
+      { pubkey: findCommitmentPda(admin.publicKey, POOL_ID, commitmentEpoch, program.programId)[0], isSigner: false, isWritable: true },
+      { pubkey: findEpochResultPda(epochResultEpoch, program.programId)[0], isSigner: false, isWritable: false },
+    ];
+  }
+
+  async function claimMany(accounts: any[]) {
+    const tx = await program.methods
+      .claimMany()
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(accounts)
+      .transaction();
+    return send(svm, tx, admin);
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda, adminAta } = await setupProgram());
+
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    price = BigInt(config.positionPrice.toString());
+
+    send(
+      svm,
+      await program.methods
+        .mintPositions(2)
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts([0, 1].flatMap((i) => positionAccountMetas(admin.publicKey, i, program.programId)))
+        .transaction(),
+      admin
+    );
+
+    await playEpoch(1, 0);
+    await playEpoch(2, 1);
+  });
+
+  it("Claims several winning epochs with one transfer", async () => {
+    const balanceBefore = tokenBalance(svm, adminAta);
+
+    const result = await claimMany([...pair(1, 1), ...pair(2, 2)]);
+    expect(result.err).to.be.undefined;
+
+    // Each epoch's pot is the single committed position
+    expect(tokenBalance(svm, adminAta) - balanceBefore).to.equal(price * BigInt(2));
+    for (const epoch of [1, 2]) {
+      const info = svm.getAccount(findCommitmentPda(admin.publicKey, POOL_ID, epoch, program.programId)[0]);
+      expect(info === null || info.lamports === 0).to.be.true;
+    }
+  });
+
+  it("Rejects a commitment paired with another epoch's result", async () => {
+    const result = await claimMany([...pair(1, 2)]);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidCommitmentAccount");
+  });
+
+  it("Rejects claiming the same commitment twice in one batch", async () => {
+    const result = await claimMany([...pair(1, 1), ...pair(1, 1)]);
+    expect(typeof result.err).to.equal("function");
+  });
+
+  it("Rejects an incomplete pair", async () => {
+    const result = await claimMany([pair(1, 1)[0]]);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("NotEnoughAccounts");
+  });
+});
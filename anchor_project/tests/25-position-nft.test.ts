@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  findPositionMintPda,
+  findPoolPda,
+  findCommitmentPda,
+  send,
+  setupProgram,
+  tokenBalance,
+} from "./utils";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+  getExtensionTypes,
+  unpackMint,
+  createAssociatedTokenAccountInstruction,
+  createTransferCheckedInstruction,
+} from "@solana/spl-token";
+
+describe("Position NFT", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+  let positionPda: PublicKey;
+  let positionMint: PublicKey;
+  let positionAta: PublicKey;
+
+  const POOL_ID = 0;
+  const EPOCH = 1;
+
+  function isClosed(address: PublicKey): boolean {
+    const info = svm.getAccount(address);
+    return info === null || info.lamports === 0;
+  }
+
+  beforeEach(async () => {
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+    [positionMint] = findPositionMintPda(positionPda, program.programId);
+    positionAta = getAssociatedTokenAddressSync(positionMint, admin.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+
+    const result = send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+  });
+
+  it("Mints a single non-transferable token with metadata", async () => {
+    expect(tokenBalance(svm, positionAta)).to.equal(BigInt(1));
+
+    const info = svm.getAccount(positionMint)!;
+    const mintState = unpackMint(positionMint, { ...info, data: Buffer.from(info.data) } as any, TOKEN_2022_PROGRAM_ID);
+    expect(mintState.supply).to.equal(BigInt(1));
+    expect(mintState.decimals).to.equal(0);
+    expect(mintState.mintAuthority).to.be.null;
+
+    const extensions = getExtensionTypes(mintState.tlvData);
+    expect(extensions).to.include(ExtensionType.NonTransferable);
+    expect(extensions).to.include(ExtensionType.MetadataPointer);
+    expect(extensions).to.include(ExtensionType.TokenMetadata);
+
+    const data = Buffer.from(info.data).toString("latin1");
+    expect(data).to.include("Position #0");
+    expect(data).to.include("global_id");
+    expect(data).to.include("created_at");
+    expect(data).to.include("TimeBased");
+  });
+
+  it("Mints even when the mint address was funded beforehand", async () => {
+    // The mint PDA is predictable, lamports sent to it must not block the position
+    const [nextPosition] = findPositionPda(admin.publicKey, 1, program.programId);
+    const [nextMint] = findPositionMintPda(nextPosition, program.programId);
+    svm.setAccount(nextMint, {
+      lamports: 1,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+
+    const result = send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: nextPosition,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+    const nextAta = getAssociatedTokenAddressSync(nextMint, admin.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    expect(tokenBalance(svm, nextAta)).to.equal(BigInt(1));
+  });
+
+  it("Rejects token transfers", async () => {
+    const other = Keypair.generate();
+    const otherAta = getAssociatedTokenAddressSync(positionMint, other.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+
+    const result = send(
+      svm,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(admin.publicKey, otherAta, other.publicKey, positionMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createTransferCheckedInstruction(positionAta, positionMint, otherAta, admin.publicKey, 1, 0, [], TOKEN_2022_PROGRAM_ID)
+      ),
+      admin
+    );
+    expect(typeof result.err).to.equal("function");
+    expect(tokenBalance(svm, positionAta)).to.equal(BigInt(1));
+  });
+
+  it("Burns the token and closes its accounts on commit", async () => {
+    const [poolPda] = findPoolPda(POOL_ID, EPOCH, program.programId);
+    send(
+      svm,
+      await program.methods
+        .initializePool(1)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+        .transaction(),
+      admin
+    );
+
+    const [commitmentPda] = findCommitmentPda(admin.publicKey, POOL_ID, EPOCH, program.programId);
+    const result = send(
+      svm,
+      await program.methods
+        .commit(new BN(0), POOL_ID)
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          commitment: commitmentPda,
+        })
+        .transaction(),
+      admin
+    );
+    expect(result.err).to.be.undefined;
+
+    expect(isClosed(positionPda)).to.be.true;
+    expect(isClosed(positionAta)).to.be.true;
+    expect(isClosed(positionMint)).to.be.true;
+  });
+});
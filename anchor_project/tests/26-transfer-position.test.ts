@@ -0,0 +1,121 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { expect } from "chai";
+import { AnchorProject } from "../target/types/anchor_project";
+import {
+  findPositionPda,
+  findPositionMintPda,
+  findUserStatePda,
+  send,
+  setupProgram,
+} from "./utils";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+
+describe("Transfer Position", () => {
+  let svm: LiteSVM;
+  let program: Program<AnchorProject>;
+  let admin: Keypair;
+  let recipient: Keypair;
+  let mint: PublicKey;
+  let configPda: PublicKey;
+  let positionPda: PublicKey;
+
+  function isClosed(address: PublicKey): boolean {
+    const info = svm.getAccount(address);
+    return info === null || info.lamports === 0;
+  }
+
+  async function setTransferable(enabled: boolean) {
+    return send(
+      svm,
+      await program.methods
+        .setTransferablePositions(enabled)
+        .accounts({ config: configPda, signer: admin.publicKey })
+        .transaction(),
+      admin
+    );
+  }
+
+  async function transferTx(from: Keypair, to: PublicKey, positionId: number, toIndex: number) {
+    const [newPosition] = findPositionPda(to, toIndex, program.programId);
+    return program.methods
+      .transferPosition(new BN(positionId))
+      .accounts({
+        signer: from.publicKey,
+        recipient: to,
+        config: configPda,
+        position: findPositionPda(from.publicKey, positionId, program.programId)[0],
+        newPosition,
+      })
+      .transaction();
+  }
+
+  beforeEach(async () => {
+    recipient = Keypair.generate();
+
+    ({ svm, program, admin, mint, configPda } = await setupProgram());
+
+    [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+    send(
+      svm,
+      await program.methods
+        .mintPosition()
+        .accounts({
+          config: configPda,
+          signer: admin.publicKey,
+          position: positionPda,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+  });
+
+  it("Rejects transfers while the flag is off", async () => {
+    const result = send(svm, await transferTx(admin, recipient.publicKey, 0, 0), admin);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("PositionsNotTransferable");
+  });
+
+  it("Rejects a transfer to the current owner", async () => {
+    await setTransferable(true);
+    const result = send(svm, await transferTx(admin, admin.publicKey, 0, 1), admin);
+    expect(typeof result.err).to.equal("function");
+    expect(result.meta().logs().join("\n")).to.include("InvalidTransferRecipient");
+  });
+
+  it("Moves the position and its token to the recipient, keeping created_at", async () => {
+    const before = program.coder.accounts.decode("positionAccount", Buffer.from(svm.getAccount(positionPda)!.data));
+    const [oldMint] = findPositionMintPda(positionPda, program.programId);
+    const oldAta = getAssociatedTokenAddressSync(oldMint, admin.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+
+    await setTransferable(true);
+    const result = send(svm, await transferTx(admin, recipient.publicKey, 0, 0), admin);
+    expect(result.err).to.be.undefined;
+
+    expect(isClosed(positionPda)).to.be.true;
+    expect(isClosed(oldMint)).to.be.true;
+    expect(isClosed(oldAta)).to.be.true;
+
+    const [newPositionPda] = findPositionPda(recipient.publicKey, 0, program.programId);
+    const after = program.coder.accounts.decode("positionAccount", Buffer.from(svm.getAccount(newPositionPda)!.data));
+    expect(after.owner.toBase58()).to.equal(recipient.publicKey.toBase58());
+    expect(after.userIndex.toNumber()).to.equal(0);
+    expect(after.globalId.toNumber()).to.equal(before.globalId.toNumber());
+    expect(after.createdAt.toNumber()).to.equal(before.createdAt.toNumber());
+
+    const [newMint] = findPositionMintPda(newPositionPda, program.programId);
+    const newAta = getAssociatedTokenAddressSync(newMint, recipient.publicKey, false, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    expect(Buffer.from(svm.getAccount(newAta)!.data).readBigUInt64LE(64)).to.equal(BigInt(1));
+
+    const [recipientState] = findUserStatePda(recipient.publicKey, program.programId);
+    const state = program.coder.accounts.decode("userState", Buffer.from(svm.getAccount(recipientState)!.data));
+    expect(state.positionCount.toNumber()).to.equal(1);
+  });
+});
@@ -12,8 +12,10 @@ import {
   findCommitmentPda,
   createAndMintToken2022,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
   WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
   logSvmResult
 } from "./utils";
 
@@ -66,7 +68,7 @@ describe("Commit Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -89,6 +91,7 @@ describe("Commit Position", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
@@ -121,6 +124,7 @@ describe("Commit Position", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -154,6 +158,7 @@ describe("Commit Position", () => {
     const commitTx = await program.methods
       .commit(new anchor.BN(0), poolId)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         commitment: commitmentPda,
@@ -195,7 +200,7 @@ describe("Commit Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -218,6 +223,7 @@ describe("Commit Position", () => {
     const mintPosition1Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position1Pda,
         mint: mint,
@@ -234,6 +240,7 @@ describe("Commit Position", () => {
     const mintPosition2Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position2Pda,
         mint: mint,
@@ -263,6 +270,7 @@ describe("Commit Position", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -288,6 +296,7 @@ describe("Commit Position", () => {
     const commit1Tx = await program.methods
       .commit(new anchor.BN(0), poolId)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position1Pda,
         commitment: commitmentPda,
@@ -313,6 +322,7 @@ describe("Commit Position", () => {
     const commit2Tx = await program.methods
       .commit(new anchor.BN(1), poolId)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position2Pda,
         commitment: commitmentPda,
@@ -345,7 +355,7 @@ describe("Commit Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -368,6 +378,7 @@ describe("Commit Position", () => {
     const mintPosition1Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position1Pda,
         mint: mint,
@@ -384,6 +395,7 @@ describe("Commit Position", () => {
     const mintPosition2Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position2Pda,
         mint: mint,
@@ -413,6 +425,7 @@ describe("Commit Position", () => {
     const initPool1Tx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -439,6 +452,7 @@ describe("Commit Position", () => {
     const commit1Tx = await program.methods
       .commit(new anchor.BN(0), poolId)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: position1Pda,
         commitment: commitment1Pda,
@@ -486,7 +500,7 @@ describe("Commit Position", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -503,6 +517,7 @@ describe("Commit Position", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
@@ -522,6 +537,7 @@ describe("Commit Position", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -561,6 +577,7 @@ describe("Commit Position", () => {
     const commitTx = await program.methods
       .commit(new anchor.BN(0), poolId)
       .accountsPartial({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         commitment: commitmentPda,
@@ -13,8 +13,10 @@ import {
   createAndMintToken2022,
   setupVrfMock,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
-  WEIGHT_RATE_DENOMINATOR
+  WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
 } from "./utils";
 
 const IDL = require("../target/idl/anchor_project.json");
@@ -27,7 +29,7 @@ function findEpochResultPda(
   const epochBuffer = Buffer.alloc(8);
   epochBuffer.writeBigUInt64LE(BigInt(epoch));
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), epochBuffer],
+    [Buffer.from("epoch_result"), findConfigPda(programId)[0].toBuffer(), epochBuffer],
     programId
   );
 }
@@ -82,7 +84,7 @@ describe("Resolve", () => {
 
     // Initialize config
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -107,6 +109,7 @@ describe("Resolve", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
@@ -139,6 +142,7 @@ describe("Resolve", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -165,6 +169,7 @@ describe("Resolve", () => {
     const commitTx = await program.methods
       .commit(new anchor.BN(0), poolId)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         commitment: commitmentPda,
@@ -247,7 +252,7 @@ describe("Resolve", () => {
 
     // Initialize
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -274,6 +279,7 @@ describe("Resolve", () => {
       const mintTx = await program.methods
         .mintPosition()
         .accounts({
+          config: configPda,
           signer: payer.publicKey,
           position: positionPda,
           mint: mint,
@@ -311,6 +317,7 @@ describe("Resolve", () => {
     const initPoolsTx = await program.methods
       .initializePool(3)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts(
@@ -330,6 +337,7 @@ describe("Resolve", () => {
     const commitTx1 = await program.methods
       .commit(new anchor.BN(0), 0)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positions[0],
         commitment: findCommitmentPda(payer.publicKey, 0, 1, program.programId)[0],
@@ -344,6 +352,7 @@ describe("Resolve", () => {
     const commitTx2 = await program.methods
       .commit(new anchor.BN(1), 1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positions[1],
         commitment: findCommitmentPda(payer.publicKey, 1, 1, program.programId)[0],
@@ -358,6 +367,7 @@ describe("Resolve", () => {
     const commitTx3 = await program.methods
       .commit(new anchor.BN(2), 1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
         position: positions[2],
         commitment: findCommitmentPda(payer.publicKey, 1, 1, program.programId)[0],
@@ -439,7 +449,7 @@ describe("Resolve", () => {
 
     // Initialize
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -462,6 +472,7 @@ describe("Resolve", () => {
     const initPoolsTx = await program.methods
       .initializePool(2)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts(
@@ -533,13 +544,67 @@ describe("Resolve", () => {
     expect(epochResult.totalPositionAmount.toNumber()).to.equal(0);
   });
 
+  it("Requires every pool of the epoch, ordered by id", async () => {
+    const initTx = await program.methods
+      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
+      .accounts({
+        signer: payer.publicKey,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    initTx.recentBlockhash = svm.latestBlockhash();
+    initTx.feePayer = payer.publicKey;
+    initTx.sign(payer);
+    svm.sendTransaction(initTx);
+
+    const [pool0Pda] = findPoolPda(0, 1, program.programId);
+    const [pool1Pda] = findPoolPda(1, 1, program.programId);
+    const toAccounts = (pubkeys: PublicKey[]) =>
+      pubkeys.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+
+    const initPoolsTx = await program.methods
+      .initializePool(2)
+      .accounts({ config: configPda, signer: payer.publicKey })
+      .remainingAccounts(toAccounts([pool0Pda, pool1Pda]))
+      .transaction();
+    initPoolsTx.recentBlockhash = svm.latestBlockhash();
+    initPoolsTx.feePayer = payer.publicKey;
+    initPoolsTx.sign(payer);
+    svm.sendTransaction(initPoolsTx);
+
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(70), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(70))
+    );
+
+    // Left out, passed twice, and out of order
+    for (const pools of [[pool0Pda], [pool0Pda, pool0Pda], [pool1Pda, pool0Pda]]) {
+      const resolveTx = await program.methods
+        .resolve(0)
+        .accounts({ signer: payer.publicKey, config: configPda })
+        .remainingAccounts(toAccounts(pools))
+        .transaction();
+      svm.expireBlockhash();
+      resolveTx.recentBlockhash = svm.latestBlockhash();
+      resolveTx.feePayer = payer.publicKey;
+      resolveTx.sign(payer);
+      const result: any = svm.sendTransaction(resolveTx);
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("InvalidPoolAccount");
+    }
+
+    const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+    expect(config.currentEpoch.toNumber()).to.equal(1);
+  });
+
   it("Prevents resolving with wrong epoch pools", async () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
 
     // Initialize
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -557,6 +622,7 @@ describe("Resolve", () => {
     const initPool0Tx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -621,6 +687,7 @@ describe("Resolve", () => {
     const initPool1Tx = await program.methods
       .initializePool(2)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -689,7 +756,7 @@ describe("Resolve", () => {
 
     // Initialize with payer as both admin and resolver
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -707,6 +774,7 @@ describe("Resolve", () => {
     const mintPositionTx = await program.methods
       .mintPosition()
       .accountsPartial({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         mint: mint,
@@ -738,6 +806,7 @@ describe("Resolve", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -764,6 +833,7 @@ describe("Resolve", () => {
     const commitTx = await program.methods
       .commit(new BN(0), poolId)
       .accountsPartial({
+        config: configPda,
         signer: payer.publicKey,
         position: positionPda,
         commitment: commitmentPda,
@@ -15,8 +15,10 @@ import {
   logSvmResult,
   setupVrfMock,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
   WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
 } from "./utils";
 import {
   TOKEN_2022_PROGRAM_ID,
@@ -34,7 +36,7 @@ function findEpochResultPda(
   const epochBuffer = Buffer.alloc(8);
   epochBuffer.writeBigUInt64LE(BigInt(epoch));
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), epochBuffer],
+    [Buffer.from("epoch_result"), findConfigPda(programId)[0].toBuffer(), epochBuffer],
     programId
   );
 }
@@ -172,7 +174,7 @@ describe("Claim", () => {
 
     // Initialize
     const initTx = await program.methods
-      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -192,6 +194,7 @@ describe("Claim", () => {
     const initPoolsTx = await program.methods
       .initializePool(2)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -218,6 +221,7 @@ describe("Claim", () => {
     const winnerMintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: winner.publicKey,
         position: winnerPosition,
         mint: mint,
@@ -235,6 +239,7 @@ describe("Claim", () => {
     const loserMintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: loser.publicKey,
         position: loserPosition,
         mint: mint,
@@ -270,6 +275,7 @@ describe("Claim", () => {
     const winnerCommitTx = await program.methods
       .commit(new BN(0), WINNING_POOL_ID)
       .accounts({
+        config: configPda,
         signer: winner.publicKey,
         position: winnerPosition,
         commitment: winnerCommitment,
@@ -291,6 +297,7 @@ describe("Claim", () => {
     const loserCommitTx = await program.methods
       .commit(new BN(0), LOSING_POOL_ID)
       .accounts({
+        config: configPda,
         signer: loser.publicKey,
         position: loserPosition,
         commitment: loserCommitment,
@@ -322,6 +329,7 @@ describe("Claim", () => {
     const resolveTx = await program.methods
       .resolve(WINNING_POOL_ID)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -366,6 +374,7 @@ describe("Claim", () => {
     const claimTx = await program.methods
       .claim(WINNING_POOL_ID, new BN(EPOCH))
       .accounts({
+        config: configPda,
         signer: winner.publicKey,
         tokenMint: mint,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -426,6 +435,7 @@ describe("Claim", () => {
       const claimTx = await program.methods
         .claim(LOSING_POOL_ID, new BN(EPOCH))
         .accounts({
+          config: configPda,
           signer: loser.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -449,6 +459,7 @@ describe("Claim", () => {
     const claimTx1 = await program.methods
       .claim(WINNING_POOL_ID, new BN(EPOCH))
       .accounts({
+        config: configPda,
         signer: winner.publicKey,
         tokenMint: mint,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -465,6 +476,7 @@ describe("Claim", () => {
       const claimTx2 = await program.methods
         .claim(WINNING_POOL_ID, new BN(EPOCH))
         .accounts({
+          config: configPda,
           signer: winner.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -489,6 +501,7 @@ describe("Claim", () => {
       const claimTx = await program.methods
         .claim(WINNING_POOL_ID, new BN(WRONG_EPOCH))
         .accounts({
+          config: configPda,
           signer: winner.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -515,6 +528,7 @@ describe("Claim", () => {
       const claimTx = await program.methods
         .claim(WINNING_POOL_ID, new BN(EPOCH))
         .accounts({
+          config: configPda,
           signer: nonParticipant.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -571,6 +585,7 @@ describe("Claim", () => {
     const claimTx = await program.methods
       .claim(WINNING_POOL_ID, new BN(EPOCH))
       .accounts({
+        config: configPda,
         signer: winner.publicKey,
         tokenMint: mint,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -5,7 +5,7 @@ import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
 import { LiteSVM, Clock } from "litesvm";
 import { expect } from "chai";
 import { AnchorProject } from "../target/types/anchor_project";
-import { findConfigPda, findPoolPda, findEpochResultPda, logSvmResult, EPOCH_DURATION, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR } from "./utils";
+import { findConfigPda, findPoolPda, findEpochResultPda, logSvmResult, EPOCH_DURATION, POSITION_PRICE, WEIGHT_RATE_NUMERATOR, WEIGHT_RATE_DENOMINATOR, INSTANCE_ID } from "./utils";
 import { createAndMintToken2022, setupVrfMock } from "./utils";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
 const IDL = require("../target/idl/anchor_project.json");
@@ -51,7 +51,7 @@ describe("Multi-Epoch Pool Initialization Flow", () => {
     const weightModel = { timeBased: {} };
     const resolutionType = { admin: {} };
     const initTx = await program.methods
-      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize(weightModel, resolutionType, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -71,6 +71,7 @@ describe("Multi-Epoch Pool Initialization Flow", () => {
     const initPool0Epoch1Tx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -141,6 +142,7 @@ describe("Multi-Epoch Pool Initialization Flow", () => {
     const initPool0Epoch2Tx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
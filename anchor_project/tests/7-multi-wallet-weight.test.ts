@@ -14,8 +14,10 @@ import {
   findEpochResultPda,
   setupVrfMock,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
-  WEIGHT_RATE_DENOMINATOR
+  WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
 } from "./utils";
 
 const IDL = require("../target/idl/anchor_project.json");
@@ -33,6 +35,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
   let program: Program<AnchorProject>;
   let payer: Keypair;
   let mint: PublicKey;
+  let configPda: PublicKey;
 
   beforeEach(async () => {
     payer = Keypair.generate();
@@ -56,6 +59,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
 
     // Create a fresh program instance instead of using the global workspace
     program = new anchor.Program<AnchorProject>(IDL as any, provider);
+    [configPda] = findConfigPda(program.programId);
 
     svm.airdrop(payer.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
     setupVrfMock(svm);
@@ -65,7 +69,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
 
     // Initialize config
     const initTx = await program.methods
-      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -143,6 +147,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
     const winner1MintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: winner1.publicKey,
         position: winner1Position,
         mint: mint,
@@ -176,6 +181,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
     const winner2MintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: winner2.publicKey,
         position: winner2Position,
         mint: mint,
@@ -209,6 +215,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
     const winner3MintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: winner3.publicKey,
         position: winner3Position,
         mint: mint,
@@ -240,6 +247,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
     const initPool0Tx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -280,6 +288,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
       const commitTx = await program.methods
         .commit(new BN(0), POOL_ID)
         .accounts({
+          config: configPda,
           signer: w.publicKey,
           position: pos,
           commitment: commitmentPda,
@@ -327,7 +336,6 @@ describe("Multi-Round Wallet Weight Distribution", () => {
     );
 
     // Resolve with pool 0 as winner
-    const [configPda] = findConfigPda(program.programId);
     const [epochResultPda] = findEpochResultPda(EPOCH, program.programId);
     const resolveTx = await program.methods
       .resolve(POOL_ID)
@@ -364,6 +372,7 @@ describe("Multi-Round Wallet Weight Distribution", () => {
       const claimTx = await program.methods
         .claim(POOL_ID, new BN(EPOCH))
         .accounts({
+          config: configPda,
           signer: w.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
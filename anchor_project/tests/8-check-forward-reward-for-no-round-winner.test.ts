@@ -16,8 +16,10 @@ import {
   setupVrfMock,
   logSvmResult,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
   WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
 } from "./utils";
 
 const IDL = require("../target/idl/anchor_project.json");
@@ -35,6 +37,7 @@ describe("Forward Rewards When No Winner", () => {
   let program: Program<AnchorProject>;
   let payer: Keypair;
   let mint: PublicKey;
+  let configPda: PublicKey;
 
   beforeEach(async () => {
     payer = Keypair.generate();
@@ -59,6 +62,7 @@ describe("Forward Rewards When No Winner", () => {
     // Create a fresh program instance instead of using the global workspace
     const programId = anchor.workspace.AnchorProject.programId;
     program = new anchor.Program<AnchorProject>(IDL as any, provider);
+    [configPda] = findConfigPda(program.programId);
 
     svm.airdrop(payer.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
     setupVrfMock(svm);
@@ -68,7 +72,7 @@ describe("Forward Rewards When No Winner", () => {
 
     // Initialize config
     const initTx = await program.methods
-      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -143,6 +147,7 @@ describe("Forward Rewards When No Winner", () => {
     const user1MintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user1.publicKey,
         position: user1Position,
         mint: mint,
@@ -167,6 +172,7 @@ describe("Forward Rewards When No Winner", () => {
     const initPoolsTx = await program.methods
       .initializePool(3)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -204,6 +210,7 @@ describe("Forward Rewards When No Winner", () => {
     const commitTx = await program.methods
       .commit(new BN(0), POOL_1)
       .accounts({
+        config: configPda,
         signer: user1.publicKey,
         position: user1Position,
         commitment: user1Commitment,
@@ -243,7 +250,6 @@ describe("Forward Rewards When No Winner", () => {
     );
 
     // Resolve epoch 1 with pool 2 as winner (pool 2 has NO participants)
-    const [configPda] = findConfigPda(program.programId);
     const [epoch1ResultPda] = findEpochResultPda(EPOCH_1, program.programId);
     const resolveEpoch1Tx = await program.methods
       .resolve(POOL_2)
@@ -254,6 +260,7 @@ describe("Forward Rewards When No Winner", () => {
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .remainingAccounts([
+        { pubkey: pool0Pda, isSigner: false, isWritable: true },
         { pubkey: pool1Pda, isSigner: false, isWritable: true },
         { pubkey: pool2Pda, isSigner: false, isWritable: true },
       ])
@@ -280,6 +287,7 @@ describe("Forward Rewards When No Winner", () => {
       const claimTx = await program.methods
         .claim(POOL_1, new BN(EPOCH_1))
         .accounts({
+          config: configPda,
           signer: user1.publicKey,
           tokenMint: mint,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -355,6 +363,7 @@ describe("Forward Rewards When No Winner", () => {
     const user2MintTx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user2.publicKey,
         position: user2Position,
         mint: mint,
@@ -387,6 +396,7 @@ describe("Forward Rewards When No Winner", () => {
     const initPool3Tx = await program.methods
       .initializePool(poolNbr)
       .accounts({
+        config: configPda,
         signer: payer.publicKey,
       })
       .remainingAccounts([
@@ -414,6 +424,7 @@ describe("Forward Rewards When No Winner", () => {
     const commitUser2Tx = await program.methods
       .commit(new BN(0), POOL_0)
       .accounts({
+        config: configPda,
         signer: user2.publicKey,
         position: user2Position,
         commitment: user2Commitment,
@@ -480,6 +491,7 @@ describe("Forward Rewards When No Winner", () => {
     const claimUser2Tx = await program.methods
       .claim(POOL_0, new BN(EPOCH_2))
       .accounts({
+        config: configPda,
         signer: user2.publicKey,
         tokenMint: mint,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
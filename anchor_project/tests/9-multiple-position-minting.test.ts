@@ -12,8 +12,10 @@ import {
   findCommitmentPda,
   createAndMintToken2022,
   EPOCH_DURATION,
+  POSITION_PRICE,
   WEIGHT_RATE_NUMERATOR,
   WEIGHT_RATE_DENOMINATOR,
+  INSTANCE_ID,
 } from "./utils";
 
 const IDL = require("../target/idl/anchor_project.json");
@@ -29,6 +31,7 @@ describe("Multiple Position Minting After Commits", () => {
   let program: Program<AnchorProject>;
   let payer: Keypair;
   let mint: PublicKey;
+  let configPda: PublicKey;
 
   beforeEach(async () => {
     payer = Keypair.generate();
@@ -51,6 +54,7 @@ describe("Multiple Position Minting After Commits", () => {
     anchor.setProvider(provider);
 
     program = new anchor.Program<AnchorProject>(IDL as any, provider);
+    [configPda] = findConfigPda(program.programId);
 
     svm.airdrop(payer.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
 
@@ -59,7 +63,7 @@ describe("Multiple Position Minting After Commits", () => {
 
     // Initialize config
     const initTx = await program.methods
-      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR))
+      .initialize({ timeBased: {} }, { admin: {} }, payer.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
       .accounts({
         signer: payer.publicKey,
         mint: mint,
@@ -83,6 +87,7 @@ describe("Multiple Position Minting After Commits", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: user.publicKey,
       })
       .remainingAccounts([
@@ -121,6 +126,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint0Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position0Pda,
         mint: mint,
@@ -152,6 +158,7 @@ describe("Multiple Position Minting After Commits", () => {
     const commit0Tx = await program.methods
       .commit(new BN(0), POOL_ID)
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position0Pda,
         commitment: commitment0Pda,
@@ -173,6 +180,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint1Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position1Pda,
         mint: mint,
@@ -204,6 +212,7 @@ describe("Multiple Position Minting After Commits", () => {
     const commit1Tx = await program.methods
       .commit(new BN(1), POOL_ID)
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position1Pda,
         commitment: commitment1Pda,
@@ -225,6 +234,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint2Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position2Pda,
         mint: mint,
@@ -260,6 +270,7 @@ describe("Multiple Position Minting After Commits", () => {
     const initPoolTx = await program.methods
       .initializePool(1)
       .accounts({
+        config: configPda,
         signer: user.publicKey,
       })
       .remainingAccounts([
@@ -281,6 +292,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint0Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position0Pda,
         mint: mint,
@@ -297,6 +309,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint1Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position1Pda,
         mint: mint,
@@ -318,6 +331,7 @@ describe("Multiple Position Minting After Commits", () => {
     const commit0Tx = await program.methods
       .commit(new BN(0), POOL_ID)
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position0Pda,
         commitment: commitment0Pda,
@@ -333,6 +347,7 @@ describe("Multiple Position Minting After Commits", () => {
     const mint2Tx = await program.methods
       .mintPosition()
       .accounts({
+        config: configPda,
         signer: user.publicKey,
         position: position2Pda,
         mint: mint,
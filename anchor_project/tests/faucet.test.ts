@@ -1,13 +1,14 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { LAMPORTS_PER_SOL, Keypair, PublicKey, Transaction } from "@solana/web3.js";
-import { getAssociatedTokenAddress, createAssociatedTokenAccountInstruction } from "@solana/spl-token";
+import { getAssociatedTokenAddress, createAssociatedTokenAccountInstruction, unpackMint } from "@solana/spl-token";
 import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
 import { LiteSVM, Clock } from "litesvm";
 import { expect } from "chai";
 import { Faucet } from "../target/types/faucet";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
 import { logSvmResult } from "./utils";
+import { createHash } from "crypto";
 
 
 const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
@@ -23,8 +24,42 @@ describe("faucet", () => {
   let mintAuthorityAta: PublicKey;
   let configPda: PublicKey;
 
+  const FAUCET_ID = 0;
+  const DECIMALS = 9;
   const INITIAL_SUPPLY = BigInt(1_000_000_000) * BigInt(10 ** 9);
   const CLAIM_AMOUNT = 10_000;
+  const CLAIM_COOLDOWN = 86_400;
+  const LIFETIME_CAP = 2 * CLAIM_AMOUNT;
+
+  async function claimAs(user: Keypair): Promise<any> {
+    const tx = await program.methods
+      .claim()
+      .accounts({
+        signer: user.publicKey,
+        config: configPda,
+      })
+      .transaction();
+    tx.recentBlockhash = svm.latestBlockhash();
+    tx.feePayer = user.publicKey;
+    tx.sign(user);
+    return svm.sendTransaction(tx);
+  }
+
+  function findFaucetConfigPda(faucetId: number): [PublicKey, number] {
+    const idBuffer = Buffer.alloc(8);
+    idBuffer.writeBigUInt64LE(BigInt(faucetId));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), idBuffer],
+      program.programId
+    );
+  }
+
+  function advanceClock(seconds: number) {
+    const c = svm.getClock();
+    svm.setClock(
+      new Clock(c.slot + BigInt(1), c.epochStartTimestamp, c.epoch, c.leaderScheduleEpoch, c.unixTimestamp + BigInt(seconds))
+    );
+  }
 
   beforeEach(async () => {
     payer = Keypair.generate();
@@ -50,13 +85,10 @@ describe("faucet", () => {
       program.programId
     );
 
-    [mint] = PublicKey.findProgramAddressSync(
-      [Buffer.from("mint"), mintAuthority.toBuffer()],
-      program.programId
-    );
+    [configPda] = findFaucetConfigPda(FAUCET_ID);
 
-    [configPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
+    [mint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), configPda.toBuffer()],
       program.programId
     );
 
@@ -71,8 +103,12 @@ describe("faucet", () => {
 
     const initTx = await program.methods
       .initialize(
+        new BN(FAUCET_ID),
+        DECIMALS,
         new BN(INITIAL_SUPPLY.toString()),
-        null
+        null,
+        null,
+        new BN(LIFETIME_CAP)
       )
       .accounts({
         signer: payer.publicKey,
@@ -93,6 +129,8 @@ describe("faucet", () => {
 
       expect(config.admin.toString()).to.equal(payer.publicKey.toString());
       expect(config.claimAmount.toNumber()).to.equal(CLAIM_AMOUNT * LAMPORTS_PER_SOL);
+      expect(config.claimCooldown.toNumber()).to.equal(CLAIM_COOLDOWN);
+      expect(config.lifetimeCap.toString()).to.equal((BigInt(LIFETIME_CAP) * BigInt(10 ** 9)).toString());
 
       const mintAuthorityAtaInfo = svm.getAccount(mintAuthorityAta);
       expect(mintAuthorityAtaInfo).to.not.be.null;
@@ -142,6 +180,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: payer.publicKey,
+          config: configPda,
         })
         .transaction();
 
@@ -172,6 +211,42 @@ describe("faucet", () => {
       expect(finalFaucetBalance.toString()).to.equal(expectedFaucetBalance.toString());
     });
 
+    it("Reopens claims once the cooldown has elapsed", async () => {
+      const newUser = Keypair.generate();
+      svm.airdrop(newUser.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+
+      expect((await claimAs(newUser)).err).to.be.undefined;
+
+      advanceClock(CLAIM_COOLDOWN - 1);
+      const early = await claimAs(newUser);
+      expect(typeof early.err).to.equal("function");
+      expect(early.meta().logs().join("\n")).to.include("AlreadyClaimed");
+
+      advanceClock(1);
+      expect((await claimAs(newUser)).err).to.be.undefined;
+
+      const [claimRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim_record"), configPda.toBuffer(), newUser.publicKey.toBuffer()],
+        program.programId
+      );
+      const record = program.coder.accounts.decode("claimRecord", Buffer.from(svm.getAccount(claimRecord)!.data));
+      expect(record.totalClaimed.toString()).to.equal((BigInt(LIFETIME_CAP) * BigInt(10 ** 9)).toString());
+    });
+
+    it("Stops claims at the lifetime cap", async () => {
+      const newUser = Keypair.generate();
+      svm.airdrop(newUser.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+
+      for (let i = 0; i < LIFETIME_CAP / CLAIM_AMOUNT; i++) {
+        expect((await claimAs(newUser)).err).to.be.undefined;
+        advanceClock(CLAIM_COOLDOWN);
+      }
+
+      const result = await claimAs(newUser);
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("LifetimeCapReached");
+    });
+
   });
 
   describe("Configuration and Logic", () => {
@@ -214,6 +289,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: newUser.publicKey,
+          config: configPda,
         })
         .transaction();
 
@@ -233,6 +309,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: newUser.publicKey,
+          config: configPda,
         })
         .transaction();
 
@@ -256,6 +333,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: newUser.publicKey,
+          config: configPda,
         })
         .instruction();
 
@@ -263,6 +341,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: newUser.publicKey,
+          config: configPda,
         })
         .instruction();
 
@@ -292,6 +371,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: payer.publicKey,
+          config: configPda,
         })
         .transaction();
 
@@ -321,6 +401,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: payer.publicKey,
+          config: configPda,
         })
         .instruction();
 
@@ -328,6 +409,7 @@ describe("faucet", () => {
         .claim()
         .accounts({
           signer: payer.publicKey,
+          config: configPda,
         })
         .instruction();
 
@@ -348,4 +430,388 @@ describe("faucet", () => {
 
 
   });
+
+  describe("Admin Management", () => {
+    function faucetBalance(): bigint {
+      return Buffer.from(svm.getAccount(mintAuthorityAta)!.data).readBigUInt64LE(64);
+    }
+
+    async function sendAs(signer: Keypair, tx: Transaction): Promise<any> {
+      tx.recentBlockhash = svm.latestBlockhash();
+      tx.feePayer = signer.publicKey;
+      tx.sign(signer);
+      return svm.sendTransaction(tx);
+    }
+
+    it("Updates the claim amount", async () => {
+      const result = await sendAs(
+        payer,
+        await program.methods.setClaimAmount(new BN(500)).accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+      expect(result.err).to.be.undefined;
+
+      const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+      expect(config.claimAmount.toNumber()).to.equal(500 * LAMPORTS_PER_SOL);
+    });
+
+    it("Refills the faucet supply", async () => {
+      const before = faucetBalance();
+      const amount = BigInt(1_000) * BigInt(10 ** 9);
+
+      const result = await sendAs(
+        payer,
+        await program.methods.refill(new BN(amount.toString())).accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+      expect(result.err).to.be.undefined;
+      expect(faucetBalance()).to.equal(before + amount);
+    });
+
+    it("Rotates the admin", async () => {
+      const newAdmin = Keypair.generate();
+      svm.airdrop(newAdmin.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+      await sendAs(
+        payer,
+        await program.methods.setAdmin(newAdmin.publicKey).accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+
+      const oldAdmin = await sendAs(
+        payer,
+        await program.methods.setClaimAmount(new BN(1)).accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+      expect(typeof oldAdmin.err).to.equal("function");
+      expect(oldAdmin.meta().logs().join("\n")).to.include("Unauthorized");
+
+      const result = await sendAs(
+        newAdmin,
+        await program.methods.setClaimAmount(new BN(1)).accounts({ signer: newAdmin.publicKey, config: configPda }).transaction()
+      );
+      expect(result.err).to.be.undefined;
+
+      // The mint's freeze authority follows the admin
+      const mintInfo = svm.getAccount(mint)!;
+      const mintState = unpackMint(mint, { ...mintInfo, data: Buffer.from(mintInfo.data) } as any, TOKEN_2022_PROGRAM_ID);
+      expect(mintState.freezeAuthority!.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+    });
+
+    it("Rejects admin instructions from other wallets", async () => {
+      const other = Keypair.generate();
+      svm.airdrop(other.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+      const result = await sendAs(
+        other,
+        await program.methods.refill(new BN(1)).accounts({ signer: other.publicKey, config: configPda }).transaction()
+      );
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("Unauthorized");
+    });
+
+    it("Drains the remaining supply to the admin and closes the faucet until refilled", async () => {
+      const remaining = faucetBalance();
+
+      const result = await sendAs(
+        payer,
+        await program.methods.drain().accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+      expect(result.err).to.be.undefined;
+
+      const adminAta = await getAssociatedTokenAddress(
+        mint,
+        payer.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      expect(Buffer.from(svm.getAccount(adminAta)!.data).readBigUInt64LE(64)).to.equal(remaining);
+
+      const vault = svm.getAccount(mintAuthorityAta);
+      expect(vault === null || vault.lamports === 0).to.be.true;
+      const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+      expect(config.drained).to.be.true;
+
+      const user = Keypair.generate();
+      svm.airdrop(user.publicKey, BigInt(LAMPORTS_PER_SOL));
+      const closed = await claimAs(user);
+      expect(typeof closed.err).to.equal("function");
+      expect(closed.meta().logs().join("\n")).to.include("FaucetDrained");
+
+      // Refilling reopens the same faucet and mint
+      const amount = BigInt(1_000) * BigInt(10 ** 9);
+      const refill = await sendAs(
+        payer,
+        await program.methods.refill(new BN(amount.toString())).accounts({ signer: payer.publicKey, config: configPda }).transaction()
+      );
+      expect(refill.err).to.be.undefined;
+      expect(faucetBalance()).to.equal(amount);
+      svm.expireBlockhash();
+      expect((await claimAs(user)).err).to.be.undefined;
+    });
+  });
+
+  describe("Multiple Faucets", () => {
+    const STABLE_ID = 1;
+    const STABLE_DECIMALS = 6;
+
+    it("Serves a second mint with its own decimals and claim records", async () => {
+      const [stableConfig] = findFaucetConfigPda(STABLE_ID);
+      const [stableMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint"), stableConfig.toBuffer()],
+        program.programId
+      );
+
+      const initTx = await program.methods
+        .initialize(new BN(STABLE_ID), STABLE_DECIMALS, new BN(1_000_000_000_000), null, null, null)
+        .accounts({ signer: payer.publicKey })
+        .transaction();
+      initTx.recentBlockhash = svm.latestBlockhash();
+      initTx.feePayer = payer.publicKey;
+      initTx.sign(payer);
+      expect(svm.sendTransaction(initTx).err).to.be.undefined;
+
+      const mintInfo = svm.getAccount(stableMint);
+      expect(mintInfo).to.not.be.null;
+      expect(Buffer.from(mintInfo!.data).readUInt8(44)).to.equal(STABLE_DECIMALS);
+
+      const newUser = Keypair.generate();
+      svm.airdrop(newUser.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+      expect((await claimAs(newUser)).err).to.be.undefined;
+
+      const claimTx = await program.methods
+        .claim()
+        .accounts({
+          signer: newUser.publicKey,
+          config: stableConfig,
+        })
+        .transaction();
+      claimTx.recentBlockhash = svm.latestBlockhash();
+      claimTx.feePayer = newUser.publicKey;
+      claimTx.sign(newUser);
+      expect(svm.sendTransaction(claimTx).err).to.be.undefined;
+
+      const userAta = await getAssociatedTokenAddress(
+        stableMint,
+        newUser.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      const balance = Buffer.from(svm.getAccount(userAta)!.data).readBigUInt64LE(64);
+      expect(balance).to.equal(BigInt(CLAIM_AMOUNT) * BigInt(10 ** STABLE_DECIMALS));
+    });
+  });
+
+  describe("Allowlist", () => {
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+    function leaf(wallet: PublicKey, amount: bigint): Buffer {
+      const amountBuffer = Buffer.alloc(8);
+      amountBuffer.writeBigUInt64LE(amount);
+      return sha256(Buffer.from([0]), wallet.toBuffer(), amountBuffer);
+    }
+
+    function node(a: Buffer, b: Buffer): Buffer {
+      const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+      return sha256(Buffer.from([1]), left, right);
+    }
+
+    let alice: Keypair;
+    let bob: Keypair;
+    let carol: Keypair;
+    const ALICE_AMOUNT = BigInt(500) * BigInt(10 ** 9);
+    const BOB_AMOUNT = BigInt(2_000) * BigInt(10 ** 9);
+    const CAROL_AMOUNT = BigInt(50) * BigInt(10 ** 9);
+    let root: Buffer;
+    let aliceProof: Buffer[];
+
+    // Claims are recorded per wallet and root, under the root currently set
+    function findAllowlistClaimPda(user: PublicKey): PublicKey {
+      const config = program.coder.accounts.decode("config", Buffer.from(svm.getAccount(configPda)!.data));
+      const currentRoot = Buffer.from(config.merkleRoot ?? Buffer.alloc(32));
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("allowlist_claim"), configPda.toBuffer(), currentRoot, user.publicKey.toBuffer()],
+        program.programId
+      )[0];
+    }
+
+    async function claimWithProof(user: Keypair, amount: bigint, proof: Buffer[]): Promise<any> {
+      const tx = await program.methods
+        .claimWithProof(new BN(amount.toString()), proof.map((p) => [...p]))
+        .accountsPartial({ signer: user.publicKey, config: configPda, allowlistClaim: findAllowlistClaimPda(user) })
+        .transaction();
+      tx.recentBlockhash = svm.latestBlockhash();
+      tx.feePayer = user.publicKey;
+      tx.sign(user);
+      return svm.sendTransaction(tx);
+    }
+
+    async function setMerkleRoot(merkleRoot: Buffer | null) {
+      const tx = await program.methods
+        .setMerkleRoot(merkleRoot ? [...merkleRoot] : null)
+        .accounts({ signer: payer.publicKey, config: configPda })
+        .transaction();
+      tx.recentBlockhash = svm.latestBlockhash();
+      tx.feePayer = payer.publicKey;
+      tx.sign(payer);
+      return svm.sendTransaction(tx);
+    }
+
+    beforeEach(() => {
+      [alice, bob, carol] = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const user of [alice, bob, carol]) {
+        svm.airdrop(user.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+      }
+
+      // Three leaves: ((alice, bob), carol)
+      const aliceLeaf = leaf(alice.publicKey, ALICE_AMOUNT);
+      const bobLeaf = leaf(bob.publicKey, BOB_AMOUNT);
+      const carolLeaf = leaf(carol.publicKey, CAROL_AMOUNT);
+      root = node(node(aliceLeaf, bobLeaf), carolLeaf);
+      aliceProof = [bobLeaf, carolLeaf];
+    });
+
+    it("Rejects proof claims while no allowlist is set", async () => {
+      const result = await claimWithProof(alice, ALICE_AMOUNT, aliceProof);
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("AllowlistDisabled");
+    });
+
+    it("Pays allowlisted wallets their amount once and closes open claims", async () => {
+      expect((await setMerkleRoot(root)).err).to.be.undefined;
+
+      const open = await claimAs(alice);
+      expect(typeof open.err).to.equal("function");
+      expect(open.meta().logs().join("\n")).to.include("AllowlistActive");
+
+      expect((await claimWithProof(alice, ALICE_AMOUNT, aliceProof)).err).to.be.undefined;
+      const aliceAta = await getAssociatedTokenAddress(
+        mint,
+        alice.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      expect(Buffer.from(svm.getAccount(aliceAta)!.data).readBigUInt64LE(64)).to.equal(ALICE_AMOUNT);
+
+      advanceClock(1);
+      const again = await claimWithProof(alice, ALICE_AMOUNT, aliceProof);
+      expect(typeof again.err).to.equal("function");
+      expect(again.meta().logs().join("\n")).to.include("AlreadyClaimed");
+
+      const carolProof = [node(leaf(alice.publicKey, ALICE_AMOUNT), leaf(bob.publicKey, BOB_AMOUNT))];
+      expect((await claimWithProof(carol, CAROL_AMOUNT, carolProof)).err).to.be.undefined;
+    });
+
+    it("Keeps a root claimed when the allowlist is switched away and back", async () => {
+      await setMerkleRoot(root);
+      expect((await claimWithProof(alice, ALICE_AMOUNT, aliceProof)).err).to.be.undefined;
+
+      // Another allowlist where alice is the only leaf
+      const otherRoot = leaf(alice.publicKey, CAROL_AMOUNT);
+      await setMerkleRoot(otherRoot);
+      expect((await claimWithProof(alice, CAROL_AMOUNT, [])).err).to.be.undefined;
+
+      svm.expireBlockhash();
+      await setMerkleRoot(root);
+      const again = await claimWithProof(alice, ALICE_AMOUNT, aliceProof);
+      expect(typeof again.err).to.equal("function");
+      expect(again.meta().logs().join("\n")).to.include("AlreadyClaimed");
+    });
+
+    it("Does not count allowlist payouts towards the lifetime cap", async () => {
+      await setMerkleRoot(root);
+      expect((await claimWithProof(alice, ALICE_AMOUNT, aliceProof)).err).to.be.undefined;
+      await setMerkleRoot(null);
+
+      // LIFETIME_CAP is two claims, both still open
+      expect((await claimAs(alice)).err).to.be.undefined;
+      advanceClock(CLAIM_COOLDOWN);
+      expect((await claimAs(alice)).err).to.be.undefined;
+    });
+
+    it("Does not rate limit the admin, as in claim", async () => {
+      const rateLimitTx = await program.methods
+        .setRateLimit(new BN(1), new BN(3_600))
+        .accounts({ signer: payer.publicKey, config: configPda })
+        .transaction();
+      rateLimitTx.recentBlockhash = svm.latestBlockhash();
+      rateLimitTx.feePayer = payer.publicKey;
+      rateLimitTx.sign(payer);
+      expect((svm.sendTransaction(rateLimitTx) as any).err).to.be.undefined;
+
+      // Admin alone on the allowlist, for far more than the window budget
+      await setMerkleRoot(leaf(payer.publicKey, BOB_AMOUNT));
+      expect((await claimWithProof(payer, BOB_AMOUNT, [])).err).to.be.undefined;
+    });
+
+    it("Rejects a proof for another amount or wallet", async () => {
+      await setMerkleRoot(root);
+
+      const wrongAmount = await claimWithProof(alice, BOB_AMOUNT, aliceProof);
+      expect(typeof wrongAmount.err).to.equal("function");
+      expect(wrongAmount.meta().logs().join("\n")).to.include("InvalidProof");
+
+      const wrongWallet = await claimWithProof(bob, ALICE_AMOUNT, aliceProof);
+      expect(typeof wrongWallet.err).to.equal("function");
+      expect(wrongWallet.meta().logs().join("\n")).to.include("InvalidProof");
+    });
+  });
+
+  describe("Rate Limiting", () => {
+    const WINDOW = 3_600;
+
+    async function setRateLimit(budget: number | null, duration: number): Promise<any> {
+      const tx = await program.methods
+        .setRateLimit(budget !== null ? new BN(budget) : null, new BN(duration))
+        .accounts({ signer: payer.publicKey, config: configPda })
+        .transaction();
+      tx.recentBlockhash = svm.latestBlockhash();
+      tx.feePayer = payer.publicKey;
+      tx.sign(payer);
+      return svm.sendTransaction(tx);
+    }
+
+    function newUser(): Keypair {
+      const user = Keypair.generate();
+      svm.airdrop(user.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+      return user;
+    }
+
+    it("Rejects claims once the window budget is spent, until it slides past them", async () => {
+      expect((await setRateLimit(2 * CLAIM_AMOUNT, WINDOW)).err).to.be.undefined;
+
+      expect((await claimAs(newUser())).err).to.be.undefined;
+      expect((await claimAs(newUser())).err).to.be.undefined;
+
+      const late = newUser();
+      const limited = await claimAs(late);
+      expect(typeof limited.err).to.equal("function");
+      expect(limited.meta().logs().join("\n")).to.include("RateLimited");
+
+      // Right after the boundary the previous window still counts in full
+      advanceClock(WINDOW);
+      svm.expireBlockhash();
+      const boundary = await claimAs(late);
+      expect(typeof boundary.err).to.equal("function");
+      expect(boundary.meta().logs().join("\n")).to.include("RateLimited");
+
+      // Half way through, only half of it does
+      advanceClock(WINDOW / 2);
+      svm.expireBlockhash();
+      expect((await claimAs(late)).err).to.be.undefined;
+    });
+
+    it("Lets the admin lift the limit", async () => {
+      await setRateLimit(CLAIM_AMOUNT, WINDOW);
+      expect((await claimAs(newUser())).err).to.be.undefined;
+
+      await setRateLimit(null, 0);
+      expect((await claimAs(newUser())).err).to.be.undefined;
+    });
+
+    it("Rejects an empty window", async () => {
+      const result = await setRateLimit(CLAIM_AMOUNT, 0);
+      expect(typeof result.err).to.equal("function");
+      expect(result.meta().logs().join("\n")).to.include("InvalidWindow");
+    });
+  });
 });
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
 import { AnchorProject } from "../target/types/anchor_project";
 import {
   TOKEN_2022_PROGRAM_ID,
@@ -8,10 +8,13 @@ import {
   createAssociatedTokenAccountInstruction,
   createMintToInstruction,
   getAssociatedTokenAddress,
+  getAssociatedTokenAddressSync,
   MINT_SIZE,
 } from "@solana/spl-token";
 import { Keypair, SystemProgram, Transaction, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
+const IDL = require("../target/idl/anchor_project.json");
 
 // VRF Program ID from ephemeral-vrf-sdk
 export const VRF_PROGRAM_ID = new PublicKey("Vrf1RNUjXmQGjmQrQLvJHs9SNkvDJEsRVFPkfSQUwGz");
@@ -20,11 +23,17 @@ export const DEFAULT_ORACLE_QUEUE = new PublicKey("4qqRrJnj4FmFSRCXPn9PZ4t4rLRKj
 
 export const EPOCH_DURATION = 60;
 
+// Price of one position in the game token's base units (1000 tokens with 9 decimals)
+export const POSITION_PRICE = 1000 * LAMPORTS_PER_SOL;
+
 // Weight rate configuration for tests
 // Default: 1 weight per 1 second (simple for testing)
 export const WEIGHT_RATE_NUMERATOR = 1;
 export const WEIGHT_RATE_DENOMINATOR = 1;
 
+// Protocol instance used by tests, every PDA below is namespaced by its config
+export const INSTANCE_ID = 0;
+
 import {
   createAssociatedTokenAccount,
   TOKEN_PROGRAM_ID,
@@ -41,16 +50,25 @@ export function getProvider() {
   return anchor.AnchorProvider.env();
 }
 
-export function findConfigPda(programId: anchor.web3.PublicKey) {
+export function findConfigPda(
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
+) {
+  const instanceBuffer = Buffer.alloc(8);
+  instanceBuffer.writeBigUInt64LE(BigInt(instanceId));
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
+    [Buffer.from("config"), instanceBuffer],
     programId
   );
 }
 
-export function findTreasuryPda(programId: anchor.web3.PublicKey) {
+export function findTreasuryPda(
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
+) {
+  const [configPda] = findConfigPda(programId, instanceId);
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("treasury")],
+    [Buffer.from("treasury"), configPda.toBuffer()],
     programId
   );
 }
@@ -86,10 +104,12 @@ export async function findUserAta(
 
 export function findUserStatePda(
   userPubkey: anchor.web3.PublicKey,
-  programId: anchor.web3.PublicKey
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
 ) {
+  const [configPda] = findConfigPda(programId, instanceId);
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("user_state"), userPubkey.toBuffer()],
+    [Buffer.from("user_state"), configPda.toBuffer(), userPubkey.toBuffer()],
     programId
   );
 }
@@ -97,27 +117,66 @@ export function findUserStatePda(
 export function findPositionPda(
   userPubkey: anchor.web3.PublicKey,
   userIndex: number,
-  programId: anchor.web3.PublicKey
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
 ) {
+  const [configPda] = findConfigPda(programId, instanceId);
   const indexBuffer = Buffer.alloc(8);
   indexBuffer.writeBigUInt64LE(BigInt(userIndex));
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("position"), userPubkey.toBuffer(), indexBuffer],
+    [Buffer.from("position"), configPda.toBuffer(), userPubkey.toBuffer(), indexBuffer],
     programId
   );
 }
 
+export function findPositionMintPda(
+  positionPda: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("position_mint"), positionPda.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * (position, position mint, position ATA) metas for one position, as expected in the
+ * remaining accounts of mint_positions and commit_many
+ */
+export function positionAccountMetas(
+  userPubkey: anchor.web3.PublicKey,
+  userIndex: number,
+  programId: anchor.web3.PublicKey
+): anchor.web3.AccountMeta[] {
+  const [positionPda] = findPositionPda(userPubkey, userIndex, programId);
+  const [positionMint] = findPositionMintPda(positionPda, programId);
+  const positionAta = getAssociatedTokenAddressSync(
+    positionMint,
+    userPubkey,
+    false,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  return [positionPda, positionMint, positionAta].map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  }));
+}
+
 export function findPoolPda(
   poolId: number,
   epoch: number,
-  programId: anchor.web3.PublicKey
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
 ) {
+  const [configPda] = findConfigPda(programId, instanceId);
   const poolIdBuffer = Buffer.alloc(1);
   poolIdBuffer.writeUInt8(poolId);
   const epochBuffer = Buffer.alloc(8);
   epochBuffer.writeBigUInt64LE(BigInt(epoch));
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("pool"), poolIdBuffer, epochBuffer],
+    [Buffer.from("pool"), configPda.toBuffer(), poolIdBuffer, epochBuffer],
     programId
   );
 }
@@ -126,26 +185,30 @@ export function findCommitmentPda(
   user: anchor.web3.PublicKey,
   poolId: number,
   epoch: number,
-  programId: anchor.web3.PublicKey
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
 ) {
+  const [configPda] = findConfigPda(programId, instanceId);
   const poolIdBuffer = Buffer.alloc(1);
   poolIdBuffer.writeUInt8(poolId);
   const epochBuffer = Buffer.alloc(8);
   epochBuffer.writeBigUInt64LE(BigInt(epoch));
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("commitment"), user.toBuffer(), poolIdBuffer, epochBuffer],
+    [Buffer.from("commitment"), configPda.toBuffer(), user.toBuffer(), poolIdBuffer, epochBuffer],
     programId
   );
 }
 
 export function findEpochResultPda(
   epoch: number,
-  programId: anchor.web3.PublicKey
+  programId: anchor.web3.PublicKey,
+  instanceId: number = INSTANCE_ID
 ) {
+  const [configPda] = findConfigPda(programId, instanceId);
   const epochBuffer = Buffer.alloc(8);
   epochBuffer.writeBigUInt64LE(BigInt(epoch));
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("epoch_result"), epochBuffer],
+    [Buffer.from("epoch_result"), configPda.toBuffer(), epochBuffer],
     programId
   );
 }
@@ -294,6 +357,149 @@ export function logSvmResult(txName: String , result: any): void {
   }
 }
 
+/**
+ * Signs `tx` with `signer` as fee payer and sends it, returns the LiteSVM result
+ */
+export function send(svm: LiteSVM, tx: Transaction, signer: Keypair): any {
+  tx.recentBlockhash = svm.latestBlockhash();
+  tx.feePayer = signer.publicKey;
+  tx.sign(signer);
+  return svm.sendTransaction(tx);
+}
+
+export interface ProgramSetup {
+  svm: LiteSVM;
+  program: Program<AnchorProject>;
+  admin: Keypair;
+  mint: PublicKey;
+  adminAta: PublicKey;
+  configPda: PublicKey;
+}
+
+/**
+ * Fresh LiteSVM with the program and the VRF mock, a funded admin holding a Token-2022 game mint,
+ * and unless `initialize` is false, instance INSTANCE_ID initialized by the admin (TimeBased, Admin resolution)
+ */
+export async function setupProgram(initialize: boolean = true): Promise<ProgramSetup> {
+  const admin = Keypair.generate();
+
+  const svm = fromWorkspace("./").withBuiltins().withSysvars();
+  const provider = new LiteSVMProvider(svm);
+  anchor.setProvider(provider);
+  const program = new anchor.Program<AnchorProject>(IDL as any, provider);
+
+  svm.airdrop(admin.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+  setupVrfMock(svm);
+
+  const { mint, recipientAta: adminAta } = await createAndMintToken2022(svm, admin, admin.publicKey, 9);
+  const [configPda] = findConfigPda(program.programId);
+
+  if (initialize) {
+    send(
+      svm,
+      await program.methods
+        .initialize({ timeBased: {} }, { admin: {} }, admin.publicKey, new BN(EPOCH_DURATION), new BN(POSITION_PRICE), new BN(WEIGHT_RATE_NUMERATOR), new BN(WEIGHT_RATE_DENOMINATOR), new BN(INSTANCE_ID))
+        .accounts({
+          signer: admin.publicKey,
+          mint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .transaction(),
+      admin
+    );
+  }
+
+  return { svm, program, admin, mint, adminAta, configPda };
+}
+
+/**
+ * Decoded Config account, the default instance unless `configPda` is given
+ */
+export function fetchConfig(svm: LiteSVM, program: Program<AnchorProject>, configPda: PublicKey = findConfigPda(program.programId)[0]) {
+  const info = svm.getAccount(configPda);
+  return program.coder.accounts.decode("config", Buffer.from(info!.data));
+}
+
+/**
+ * Token amount held by a (Token-2022) token account, 0 when the account does not exist
+ */
+export function tokenBalance(svm: LiteSVM, ata: PublicKey): bigint {
+  const info = svm.getAccount(ata);
+  return info ? Buffer.from(info.data).readBigUInt64LE(64) : BigInt(0);
+}
+
+/**
+ * Decoded EpochResult of `epoch` for the default instance, null when it was never opened
+ */
+export function fetchEpochResult(svm: LiteSVM, program: Program<AnchorProject>, epoch: number) {
+  const info = svm.getAccount(findEpochResultPda(epoch, program.programId)[0]);
+  return info ? program.coder.accounts.decode("epochResult", Buffer.from(info.data)) : null;
+}
+
+/**
+ * Mints the admin's first position, opens `epoch` with the single pool `poolId` and commits the position to it
+ * Returns the result of the commit transaction
+ */
+export async function commitFirstPosition(setup: ProgramSetup, poolId: number, epoch: number): Promise<any> {
+  const { svm, program, admin, mint, configPda } = setup;
+
+  const [positionPda] = findPositionPda(admin.publicKey, 0, program.programId);
+  send(
+    svm,
+    await program.methods
+      .mintPosition()
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        position: positionPda,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction(),
+    admin
+  );
+
+  const [poolPda] = findPoolPda(poolId, epoch, program.programId);
+  send(
+    svm,
+    await program.methods
+      .initializePool(1)
+      .accounts({ config: configPda, signer: admin.publicKey })
+      .remainingAccounts([{ pubkey: poolPda, isSigner: false, isWritable: true }])
+      .transaction(),
+    admin
+  );
+
+  const [commitmentPda] = findCommitmentPda(admin.publicKey, poolId, epoch, program.programId);
+  return send(
+    svm,
+    await program.methods
+      .commit(new BN(0), poolId)
+      .accounts({
+        config: configPda,
+        signer: admin.publicKey,
+        position: positionPda,
+        commitment: commitmentPda,
+      })
+      .transaction(),
+    admin
+  );
+}
+
+/**
+ * Decodes the events a transaction emitted with emit_cpi!
+ * They travel as self-CPI data: 8 byte event ix tag + event
+ */
+export function decodeCpiEvents(program: Program<AnchorProject>, result: any): any[] {
+  return result
+    .innerInstructions()
+    .flat()
+    .map((inner: any) => Buffer.from(inner.instruction().data()))
+    .filter((data: Buffer) => data.length > 8)
+    .map((data: Buffer) => program.coder.events.decode(data.subarray(8).toString("base64")))
+    .filter((event: any) => event !== null);
+}
+
 /**
  * Sets up mock accounts for VRF program in LiteSVM
  * Required when using the #[vrf] macro from ephemeral-vrf-sdk